@@ -40,7 +40,7 @@ pub fn download_dts(
 
     let response = minreq::get(url).send()?;
     let body = response.as_str()?;
-    std::fs::write(&dts_file, body)?;
+    std::fs::write(dts_file, body)?;
 
     Ok(())
 }
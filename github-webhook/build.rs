@@ -9,7 +9,7 @@ use cargo_metadata::{CargoOpt, MetadataCommand};
 
 use github_webhook_dts_downloader::download_dts;
 
-use github_webhook_type_generator::dts2rs;
+use github_webhook_type_generator::{dts2modules, Opt};
 
 fn main() -> Result<()> {
     println!("cargo:rerun-if-env-changed=GITHUB_WEBHOOK_SCHEMA_DTS");
@@ -60,7 +60,17 @@ fn main() -> Result<()> {
         })?;
     }
 
-    let rs = dts2rs(&dts_file);
+    // without `all-events`, only events whose feature is enabled
+    let all_events = env::var_os("CARGO_FEATURE_ALL_EVENTS").is_some();
+    let modules = dts2modules(&dts_file, Opt::default(), move |event| {
+        all_events || env::var_os(format!("CARGO_FEATURE_{}", event.to_uppercase())).is_some()
+    });
+    for event in modules.events.keys() {
+        if !pkg.features.contains_key(event) {
+            println!("cargo:warning=no feature for event `{event}`, use `all-events` instead");
+        }
+    }
+    let rs = modules.into_tokens();
     let rs_file = out_dir.join("types.rs");
 
     let mut writer = BufWriter::new(File::create(&rs_file)?);
@@ -0,0 +1,133 @@
+//! receive deliveries locally while developing, without a tunnel
+//!
+//! ```sh
+//! github-webhook-dev-server [--addr 127.0.0.1:3000] [--secret <secret>] [--save-dir <dir>]
+//! ```
+//!
+//! prints each delivery decoded into the generated types, or where it fails to parse.
+//! with a secret (`--secret` or `GITHUB_WEBHOOK_SECRET`), deliveries without a valid
+//! `X-Hub-Signature-256` are rejected. with `--save-dir`, raw deliveries are stored for
+//! `github-webhook-validate` and replaying.
+
+use std::{env, path::PathBuf, process::ExitCode};
+
+use github_webhook::{delivery::Delivery, payload_types::Schema, signature};
+use tiny_http::{Method, Request, Response, Server};
+
+const USAGE: &str =
+    "usage: github-webhook-dev-server [--addr <addr>] [--secret <secret>] [--save-dir <dir>]";
+
+struct Config {
+    secret: Option<String>,
+    save_dir: Option<PathBuf>,
+}
+
+fn main() -> ExitCode {
+    let mut addr = "127.0.0.1:3000".to_owned();
+    let mut config = Config {
+        secret: env::var("GITHUB_WEBHOOK_SECRET").ok(),
+        save_dir: None,
+    };
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let value = match arg.as_str() {
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                return ExitCode::SUCCESS;
+            }
+            "--addr" | "--secret" | "--save-dir" => match args.next() {
+                Some(value) => value,
+                None => return usage(),
+            },
+            _ => return usage(),
+        };
+        match arg.as_str() {
+            "--addr" => addr = value,
+            "--secret" => config.secret = Some(value),
+            _ => config.save_dir = Some(PathBuf::from(value)),
+        }
+    }
+
+    let server = match Server::http(&addr) {
+        Ok(server) => server,
+        Err(e) => {
+            eprintln!("failed to listen on {addr}: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    if config.secret.is_none() {
+        eprintln!("no secret given, signatures are not verified");
+    }
+    eprintln!("listening on http://{addr}");
+    for request in server.incoming_requests() {
+        let (status, message) = handle(&config, request);
+        eprintln!("-> {status} {message}");
+    }
+    ExitCode::SUCCESS
+}
+
+fn usage() -> ExitCode {
+    eprintln!("{USAGE}");
+    ExitCode::from(2)
+}
+
+/// respond to `request`, returning the status and its reason
+fn handle(config: &Config, mut request: Request) -> (u16, String) {
+    println!("{} {}", request.method(), request.url());
+    let (status, message) = match receive(config, &mut request) {
+        Ok(message) => (200, message),
+        Err(error) => error,
+    };
+    if let Err(e) = request.respond(Response::from_string(&message).with_status_code(status)) {
+        eprintln!("failed to respond: {e}");
+    }
+    (status, message)
+}
+
+fn receive(config: &Config, request: &mut Request) -> Result<String, (u16, String)> {
+    if *request.method() != Method::Post {
+        return Err((405, "deliveries are POST".to_owned()));
+    }
+    let mut delivery = Delivery {
+        headers: request
+            .headers()
+            .iter()
+            .map(|h| (h.field.to_string(), h.value.to_string()))
+            .collect(),
+        body: Vec::new(),
+    };
+    request
+        .as_reader()
+        .read_to_end(&mut delivery.body)
+        .map_err(|e| (400, format!("failed to read body: {e}")))?;
+
+    if let Some(secret) = &config.secret {
+        let valid = delivery.header(signature::HEADER).map_or(false, |s| {
+            signature::verify(secret.as_bytes(), &delivery.body, s)
+        });
+        if !valid {
+            return Err((401, format!("missing or invalid {}", signature::HEADER)));
+        }
+    }
+    if let Some(dir) = &config.save_dir {
+        match delivery.save(dir) {
+            Ok(path) => println!("saved to {}", path.display()),
+            Err(e) => eprintln!("failed to save to {}: {e}", dir.display()),
+        }
+    }
+
+    let body = std::str::from_utf8(&delivery.body)
+        .map_err(|e| (400, format!("body is not UTF-8: {e}")))?;
+    let schema = match delivery.event() {
+        Some(event) => github_webhook::parse(event, body).map_err(|e| (400, e.to_string()))?,
+        None => serde_json::from_str::<Schema>(body).map_err(|e| {
+            let message = format!("{e}, send `X-GitHub-Event` to locate the error");
+            (400, message)
+        })?,
+    };
+    println!("{schema:#?}");
+    Ok(match schema.action() {
+        Some(action) => format!("{} ({action})", schema.event_name()),
+        None => schema.event_name().to_owned(),
+    })
+}
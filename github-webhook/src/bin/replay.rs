@@ -0,0 +1,113 @@
+//! send stored deliveries to a webhook endpoint again
+//!
+//! ```sh
+//! github-webhook-replay [options] <url> <delivery.json | directory>...
+//! ```
+//!
+//! deliveries are those saved by `github-webhook-dev-server` or bare payloads, e.g. examples
+//! of octokit/webhooks with `--as-event`. directories are searched for `*.json` recursively.
+
+use std::{env, path::PathBuf, process::ExitCode, time::Duration};
+
+use github_webhook::{
+    delivery::{self, Delivery},
+    replay::{self, Options},
+};
+
+const USAGE: &str = "usage: github-webhook-replay [options] <url> <delivery.json | directory>...
+
+options:
+    --secret <secret>     re-sign deliveries (default: GITHUB_WEBHOOK_SECRET)
+    --as-event <name>     X-GitHub-Event of payloads without one
+    --event <name>        send only the event, repeatable
+    --action <name>       send only payloads with the action, repeatable
+    --interval <ms>       wait between deliveries (default: 0)";
+
+fn main() -> ExitCode {
+    let mut opt = Options {
+        secret: env::var("GITHUB_WEBHOOK_SECRET").ok(),
+        ..Options::default()
+    };
+    let mut positional = Vec::new();
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "-h" || arg == "--help" {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        if !arg.starts_with("--") {
+            positional.push(arg);
+            continue;
+        }
+        let Some(value) = args.next() else {
+            return usage();
+        };
+        match arg.as_str() {
+            "--secret" => opt.secret = Some(value),
+            "--as-event" => opt.event = Some(value),
+            "--event" => opt.events.push(value),
+            "--action" => opt.actions.push(value),
+            "--interval" => match value.parse() {
+                Ok(ms) => opt.interval = Duration::from_millis(ms),
+                Err(_) => return usage(),
+            },
+            _ => return usage(),
+        }
+    }
+    let (url, paths) = match positional.split_first() {
+        Some((url, paths)) if !paths.is_empty() => (url, paths),
+        _ => return usage(),
+    };
+
+    let mut deliveries = Vec::new();
+    for path in paths.iter().map(PathBuf::from) {
+        let loaded = delivery::collect(&path).and_then(|files| {
+            files
+                .iter()
+                .map(Delivery::load)
+                .collect::<Result<Vec<_>, _>>()
+        });
+        match loaded {
+            Ok(loaded) => deliveries.extend(loaded),
+            Err(e) => {
+                eprintln!("{}: {e}", path.display());
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    let (mut sent, mut failed) = (0, 0);
+    for (delivery, result) in replay::replay(url, deliveries, &opt) {
+        sent += 1;
+        let name = match (delivery.event(), delivery.action()) {
+            (Some(event), Some(action)) => format!("{event} ({action})"),
+            (Some(event), None) => event.to_owned(),
+            (None, _) => "unknown event".to_owned(),
+        };
+        match result {
+            Ok(response) if (200..300).contains(&response.status_code) => {
+                println!("{} {name}", response.status_code)
+            }
+            Ok(response) => {
+                failed += 1;
+                let body = response.as_str().unwrap_or_default();
+                println!("{} {name}: {body}", response.status_code);
+            }
+            Err(e) => {
+                failed += 1;
+                println!("FAILED {name}: {e}");
+            }
+        }
+    }
+    println!("{} ok, {failed} failed", sent - failed);
+    if failed == 0 {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+fn usage() -> ExitCode {
+    eprintln!("{USAGE}");
+    ExitCode::from(2)
+}
@@ -0,0 +1,113 @@
+//! check captured payloads against the generated types
+//!
+//! ```sh
+//! github-webhook-validate [--event <name>] <payload.json | directory>...
+//! ```
+//!
+//! directories are searched for `*.json` recursively. with `--event` (e.g. `pull_request`),
+//! payloads are deserialized into the type of the event, which locates errors better than
+//! `Schema` trying every event. otherwise the event of deliveries saved by
+//! `github-webhook-dev-server` is read from their `.headers`, and other payloads are parsed as
+//! every event, reporting the event whose error is the deepest.
+
+use std::{
+    env,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use github_webhook::{
+    delivery::{self, Delivery},
+    payload_types::Schema,
+};
+
+const USAGE: &str = "usage: github-webhook-validate [--event <name>] <payload.json | directory>...";
+
+fn main() -> ExitCode {
+    let mut event = None;
+    let mut paths = Vec::new();
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--event" => match args.next() {
+                Some(e) => event = Some(e),
+                None => return usage(),
+            },
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                return ExitCode::SUCCESS;
+            }
+            _ => paths.push(PathBuf::from(arg)),
+        }
+    }
+    if paths.is_empty() {
+        return usage();
+    }
+
+    let mut files = Vec::new();
+    for path in &paths {
+        match delivery::collect(path) {
+            Ok(found) => files.extend(found),
+            Err(e) => {
+                eprintln!("{}: {e}", path.display());
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    let mut failed = 0;
+    for file in &files {
+        match validate(file, event.as_deref()) {
+            Ok(summary) => println!("ok {}: {summary}", file.display()),
+            Err(e) => {
+                failed += 1;
+                println!("FAILED {}: {e}", file.display());
+            }
+        }
+    }
+    println!("{} ok, {failed} failed", files.len() - failed);
+    if failed == 0 {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+fn usage() -> ExitCode {
+    eprintln!("{USAGE}");
+    ExitCode::from(2)
+}
+
+/// event and action of the payload, or where and why it does not match
+fn validate(file: &Path, event: Option<&str>) -> Result<String, String> {
+    let delivery = Delivery::load(file).map_err(|e| e.to_string())?;
+    let json = std::str::from_utf8(&delivery.body).map_err(|e| e.to_string())?;
+    let schema = match event.or(delivery.event()) {
+        Some(event) => github_webhook::parse(event, json).map_err(|e| e.to_string())?,
+        None => parse_any(json)?,
+    };
+    Ok(match schema.action() {
+        Some(action) => format!("{} ({action})", schema.event_name()),
+        None => schema.event_name().to_owned(),
+    })
+}
+
+/// parse as each event, reporting the error which went deepest if none matches
+fn parse_any(json: &str) -> Result<Schema, String> {
+    let mut closest: Option<github_webhook::ParseError> = None;
+    for event in Schema::EVENT_NAMES {
+        match github_webhook::parse(event, json) {
+            Ok(schema) => return Ok(schema),
+            Err(e) => {
+                let depth = |e: &github_webhook::ParseError| e.pointer.matches('/').count();
+                if closest.as_ref().map_or(true, |c| depth(c) < depth(&e)) {
+                    closest = Some(e);
+                }
+            }
+        }
+    }
+    Err(match closest {
+        Some(e) => format!("{e} (closest event, try `--event` if it is another one)"),
+        None => "no events are enabled".to_owned(),
+    })
+}
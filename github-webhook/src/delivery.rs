@@ -0,0 +1,163 @@
+//! raw deliveries stored on disk, e.g. to replay them later
+//!
+//! a delivery is stored as `<id>.json` (body as received) and `<id>.headers` (one
+//! `Name: value` per line) where `<id>` is `X-GitHub-Delivery`. the `.json` alone is also
+//! loadable, e.g. payload examples of octokit/webhooks.
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+pub const EVENT_HEADER: &str = "X-GitHub-Event";
+pub const ID_HEADER: &str = "X-GitHub-Delivery";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Delivery {
+    /// in received order
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl Delivery {
+    /// value of the first header named `name`, case-insensitively
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// replace the headers named `name`, or add one
+    pub fn set_header(&mut self, name: &str, value: impl Into<String>) {
+        self.headers.retain(|(n, _)| !n.eq_ignore_ascii_case(name));
+        self.headers.push((name.to_owned(), value.into()));
+    }
+
+    /// `X-GitHub-Event`, e.g. `pull_request`
+    pub fn event(&self) -> Option<&str> {
+        self.header(EVENT_HEADER)
+    }
+
+    /// `X-GitHub-Delivery`
+    pub fn id(&self) -> Option<&str> {
+        self.header(ID_HEADER)
+    }
+
+    /// `action` of the body, if any
+    pub fn action(&self) -> Option<String> {
+        #[derive(serde::Deserialize)]
+        struct Action {
+            action: Option<String>,
+        }
+        serde_json::from_slice::<Action>(&self.body).ok()?.action
+    }
+
+    /// write to `dir`, returning the path of the body.
+    /// deliveries without id are named after the current time.
+    pub fn save(&self, dir: impl AsRef<Path>) -> io::Result<PathBuf> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir)?;
+        let name = match self.id() {
+            // ids are UUIDs, but the header comes from the client
+            Some(id)
+                if !id.is_empty() && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') =>
+            {
+                id.to_owned()
+            }
+            _ => {
+                let now = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default();
+                format!("{}{:09}", now.as_secs(), now.subsec_nanos())
+            }
+        };
+        let body = dir.join(format!("{name}.json"));
+        fs::write(&body, &self.body)?;
+        let mut headers = String::new();
+        for (n, v) in &self.headers {
+            headers.push_str(&format!("{n}: {v}\n"));
+        }
+        fs::write(body.with_extension("headers"), headers)?;
+        Ok(body)
+    }
+
+    /// read the body at `path` and the headers next to it, if saved
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        let body = fs::read(path)?;
+        let headers = match fs::read_to_string(path.with_extension("headers")) {
+            Ok(headers) => parse_headers(&headers)?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(e),
+        };
+        Ok(Self { headers, body })
+    }
+}
+
+/// `path` itself if a file, or bodies (`*.json`) under it recursively, in order
+pub fn collect(path: impl AsRef<Path>) -> io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    collect_json(path.as_ref(), &mut files)?;
+    Ok(files)
+}
+
+fn collect_json(path: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    if !path.is_dir() {
+        files.push(path.to_owned());
+        return Ok(());
+    }
+    let mut entries = fs::read_dir(path)?
+        .map(|e| e.map(|e| e.path()))
+        .collect::<Result<Vec<_>, _>>()?;
+    entries.sort();
+    for entry in entries {
+        if entry.is_dir() {
+            collect_json(&entry, files)?;
+        } else if entry.extension().map_or(false, |e| e == "json") {
+            files.push(entry);
+        }
+    }
+    Ok(())
+}
+
+fn parse_headers(headers: &str) -> io::Result<Vec<(String, String)>> {
+    headers
+        .lines()
+        .filter(|l| !l.is_empty())
+        .map(|l| match l.split_once(':') {
+            Some((n, v)) => Ok((n.trim().to_owned(), v.trim().to_owned())),
+            None => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid header line `{l}`"),
+            )),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_save_load() {
+        let dir =
+            std::env::temp_dir().join(format!("github-webhook-delivery-{}", std::process::id()));
+        let mut delivery = Delivery {
+            headers: vec![("x-github-event".to_owned(), "issues".to_owned())],
+            body: br#"{"action":"opened"}"#.to_vec(),
+        };
+        delivery.set_header(ID_HEADER, "72d3162e-cc78-11e3-81ab-4c9367dc0958");
+        assert_eq!(delivery.event(), Some("issues"));
+        assert_eq!(delivery.action().as_deref(), Some("opened"));
+
+        let path = delivery.save(&dir).unwrap();
+        assert_eq!(
+            path.file_name().unwrap(),
+            "72d3162e-cc78-11e3-81ab-4c9367dc0958.json"
+        );
+        assert_eq!(Delivery::load(&path).unwrap(), delivery);
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -1 +1,10 @@
+pub mod delivery;
+mod expect;
+mod parse;
 pub mod payload_types;
+#[cfg(feature = "replay")]
+pub mod replay;
+#[cfg(feature = "signature")]
+pub mod signature;
+
+pub use parse::{parse, ParseError, ParseErrorKind};
@@ -0,0 +1,189 @@
+use std::fmt;
+
+use serde_path_to_error::Segment;
+
+use crate::{expect, payload_types::Schema};
+
+/// longest [`ParseError::snippet`] in chars
+const SNIPPET_LEN: usize = 80;
+
+/// parse the payload of `event` (as in `X-GitHub-Event` header, e.g. `pull_request`).
+///
+/// the payload is deserialized into the type of the event (and of its `action`, if the event
+/// has actions), so errors tell the failing field unlike deserializing [`Schema`] which tries
+/// every event.
+// `Schema` borrows from `body` unless no enabled event has strings, hence no explicit lifetime
+pub fn parse(event: impl AsRef<str>, body: &str) -> Result<Schema, ParseError> {
+    #[derive(serde::Deserialize)]
+    struct Action {
+        action: Option<String>,
+    }
+
+    let event = event.as_ref();
+    let action = serde_json::from_str::<Action>(body)
+        .ok()
+        .and_then(|a| a.action);
+    let expected = expect::Expected::default();
+    let mut de = serde_json::Deserializer::from_str(body);
+    let mut track = serde_path_to_error::Track::new();
+    let tracked = serde_path_to_error::Deserializer::new(
+        expect::Deserializer::new(&mut de, &expected),
+        &mut track,
+    );
+    let parsed =
+        Schema::deserialize_event_action(event, action.as_deref(), tracked).and_then(|s| match s {
+            Some(s) => de.end().map(|_| Some(s)),
+            None => Ok(None),
+        });
+    match parsed {
+        Ok(Some(schema)) => Ok(schema),
+        Ok(None) => Err(ParseError {
+            event: event.to_owned(),
+            pointer: String::new(),
+            expected: None,
+            snippet: None,
+            kind: ParseErrorKind::UnknownEvent,
+        }),
+        Err(e) => {
+            let pointer = json_pointer(&track.path());
+            let snippet = serde_json::from_str::<serde_json::Value>(body)
+                .ok()
+                .and_then(|v| v.pointer(&pointer).map(snippet));
+            Err(ParseError {
+                event: event.to_owned(),
+                pointer,
+                expected: expected.into_inner(),
+                snippet,
+                kind: ParseErrorKind::Json(e),
+            })
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ParseError {
+    /// event name given to [`parse`]
+    pub event: String,
+    /// JSON pointer of the failing value, e.g. `/pull_request/user/login`.
+    /// for missing fields, the object lacking them.
+    pub pointer: String,
+    /// what the Rust type at [`ParseError::pointer`] expected, e.g. `usize` or
+    /// `struct PullRequest`, if the error tells
+    pub expected: Option<String>,
+    /// the failing value in JSON, shortened
+    pub snippet: Option<String>,
+    pub kind: ParseErrorKind,
+}
+
+#[derive(Debug)]
+pub enum ParseErrorKind {
+    /// the event is not in the schema, or its feature is disabled
+    UnknownEvent,
+    Json(serde_json::Error),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self {
+            event,
+            pointer,
+            snippet,
+            kind,
+            ..
+        } = self;
+        match kind {
+            ParseErrorKind::UnknownEvent => write!(f, "unknown event `{event}`"),
+            ParseErrorKind::Json(e) => {
+                write!(f, "failed to parse `{event}` payload at `{pointer}`: {e}")?;
+                if let Some(snippet) = snippet {
+                    write!(f, ", found {snippet}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match &self.kind {
+            ParseErrorKind::UnknownEvent => None,
+            ParseErrorKind::Json(e) => Some(e),
+        }
+    }
+}
+
+/// RFC 6901 pointer of the path
+fn json_pointer(path: &serde_path_to_error::Path) -> String {
+    let mut pointer = String::new();
+    for segment in path.iter() {
+        match segment {
+            Segment::Seq { index } => pointer.push_str(&format!("/{index}")),
+            Segment::Map { key } => {
+                pointer.push('/');
+                pointer.push_str(&key.replace('~', "~0").replace('/', "~1"));
+            }
+            // variants of the generated enums are not in the payload
+            Segment::Enum { .. } | Segment::Unknown => (),
+        }
+    }
+    pointer
+}
+
+fn snippet(value: &serde_json::Value) -> String {
+    let json = value.to_string();
+    if json.chars().count() <= SNIPPET_LEN {
+        return json;
+    }
+    let mut short: String = json.chars().take(SNIPPET_LEN).collect();
+    short.push_str("...");
+    short
+}
+
+#[cfg(all(
+    test,
+    any(feature = "all-events", feature = "ping", feature = "issues")
+))]
+mod tests {
+    use super::*;
+
+    #[cfg(any(feature = "all-events", feature = "ping"))]
+    #[test]
+    fn test_parse() {
+        const PING: &str = r#"{
+            "zen": "Keep it logically awesome.",
+            "hook_id": 1,
+            "hook": {
+                "type": "Repository",
+                "id": "1",
+                "name": "web",
+                "active": true,
+                "events": ["push"],
+                "config": {},
+                "updated_at": "2024-01-01T00:00:00Z",
+                "created_at": "2024-01-01T00:00:00Z"
+            }
+        }"#;
+
+        let e = parse("ping", PING).unwrap_err();
+        assert_eq!(e.pointer, "/hook/id");
+        assert_eq!(e.expected.as_deref(), Some("usize"));
+        assert_eq!(e.snippet.as_deref(), Some(r#""1""#));
+        assert!(e
+            .to_string()
+            .starts_with("failed to parse `ping` payload at `/hook/id`"));
+
+        let e = parse("unknown", PING).unwrap_err();
+        assert!(matches!(e.kind, ParseErrorKind::UnknownEvent));
+    }
+
+    #[cfg(any(feature = "all-events", feature = "issues"))]
+    #[test]
+    fn test_parse_action() {
+        let body = r#"{ "action": "opened", "sender": { "login": "octocat", "id": "1" } }"#;
+        let e = parse("issues", body).unwrap_err();
+        assert_eq!(e.pointer, "/sender/id");
+        assert_eq!(e.expected.as_deref(), Some("usize"));
+        assert_eq!(e.snippet.as_deref(), Some(r#""1""#));
+    }
+}
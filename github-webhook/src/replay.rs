@@ -0,0 +1,204 @@
+//! send stored deliveries to an endpoint again, e.g. a handler running locally
+
+use std::{thread, time::Duration};
+
+use crate::{
+    delivery::{Delivery, EVENT_HEADER},
+    signature,
+};
+
+/// headers of the original request which do not apply to the replayed one
+const DROPPED_HEADERS: &[&str] = &[
+    "Host",
+    "Content-Length",
+    "Connection",
+    "Transfer-Encoding",
+    // SHA-1 of the original secret
+    "X-Hub-Signature",
+];
+
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    /// re-sign deliveries, replacing `X-Hub-Signature-256`. otherwise sent as stored.
+    pub secret: Option<String>,
+    /// `X-GitHub-Event` of deliveries without one, e.g. payload examples
+    pub event: Option<String>,
+    /// send only these events, all if empty
+    pub events: Vec<String>,
+    /// send only payloads with these actions, all if empty
+    pub actions: Vec<String>,
+    /// wait between deliveries
+    pub interval: Duration,
+}
+
+impl Options {
+    /// whether `delivery` passes [`Options::events`] and [`Options::actions`]
+    pub fn matches(&self, delivery: &Delivery) -> bool {
+        let event = delivery.event().or(self.event.as_deref());
+        if !self.events.is_empty() && !event.map_or(false, |e| self.events.iter().any(|f| f == e)) {
+            return false;
+        }
+        self.actions.is_empty()
+            || delivery
+                .action()
+                .map_or(false, |a| self.actions.contains(&a))
+    }
+
+    /// `delivery` with the headers to send
+    pub fn prepare(&self, mut delivery: Delivery) -> Delivery {
+        delivery
+            .headers
+            .retain(|(n, _)| !DROPPED_HEADERS.iter().any(|d| n.eq_ignore_ascii_case(d)));
+        if delivery.event().is_none() {
+            if let Some(event) = &self.event {
+                delivery.set_header(EVENT_HEADER, event);
+            }
+        }
+        if delivery.header("Content-Type").is_none() {
+            delivery.set_header("Content-Type", "application/json");
+        }
+        if delivery.header("User-Agent").is_none() {
+            delivery.set_header("User-Agent", "GitHub-Hookshot/replay");
+        }
+        if let Some(secret) = &self.secret {
+            let signature = signature::sign(secret.as_bytes(), &delivery.body);
+            delivery.set_header(signature::HEADER, signature);
+        }
+        delivery
+    }
+}
+
+/// POST `delivery` as is to `url`
+pub fn send(url: &str, delivery: &Delivery) -> Result<minreq::Response, minreq::Error> {
+    let mut request = minreq::post(url);
+    for (name, value) in &delivery.headers {
+        request = request.with_header(name, value);
+    }
+    request.with_body(delivery.body.clone()).send()
+}
+
+/// send the deliveries matching `opt` to `url` in order, waiting [`Options::interval`]
+/// between them. deliveries are sent as the iterator is advanced.
+pub fn replay<'a>(
+    url: &'a str,
+    deliveries: impl IntoIterator<Item = Delivery> + 'a,
+    opt: &'a Options,
+) -> impl Iterator<Item = (Delivery, Result<minreq::Response, minreq::Error>)> + 'a {
+    deliveries
+        .into_iter()
+        .filter(|d| opt.matches(d))
+        .enumerate()
+        .map(|(i, delivery)| {
+            if i != 0 {
+                thread::sleep(opt.interval);
+            }
+            let delivery = opt.prepare(delivery);
+            let result = send(url, &delivery);
+            (delivery, result)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn delivery(event: Option<&str>, body: &str) -> Delivery {
+        Delivery {
+            headers: event
+                .map(|e| vec![(EVENT_HEADER.to_owned(), e.to_owned())])
+                .unwrap_or_default(),
+            body: body.as_bytes().to_vec(),
+        }
+    }
+
+    /// replay to a local server, returning the requests it received as deliveries
+    fn replay_locally(deliveries: Vec<Delivery>, opt: &Options) -> Vec<Delivery> {
+        let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/webhook", server.server_addr().to_ip().unwrap());
+        let receiver = thread::spawn(move || {
+            let mut received = Vec::new();
+            // until the empty body sent after replaying
+            while let Ok(mut request) = server.recv() {
+                let mut body = Vec::new();
+                request.as_reader().read_to_end(&mut body).unwrap();
+                if body.is_empty() {
+                    break;
+                }
+                received.push(Delivery {
+                    headers: request
+                        .headers()
+                        .iter()
+                        .map(|h| (h.field.to_string(), h.value.to_string()))
+                        .collect(),
+                    body,
+                });
+                request.respond(tiny_http::Response::empty(204)).unwrap();
+            }
+            received
+        });
+        let statuses: Vec<i32> = replay(&url, deliveries, opt)
+            .map(|(_, r)| r.unwrap().status_code)
+            .collect();
+        assert!(statuses.iter().all(|s| *s == 204));
+        // stop the server
+        let _ = send(&url, &Delivery::default());
+        receiver.join().unwrap()
+    }
+
+    fn bodies(received: &[Delivery]) -> Vec<&str> {
+        received
+            .iter()
+            .map(|d| std::str::from_utf8(&d.body).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_replay() {
+        let opt = Options {
+            secret: Some("secret".to_owned()),
+            events: vec!["issues".to_owned()],
+            interval: Duration::from_millis(10),
+            ..Options::default()
+        };
+        let deliveries = vec![
+            delivery(Some("issues"), r#"{"action":"opened"}"#),
+            delivery(Some("push"), "{}"),
+        ];
+        let received = replay_locally(deliveries, &opt);
+        assert_eq!(bodies(&received), [r#"{"action":"opened"}"#]);
+        let sig = received[0].header(signature::HEADER).unwrap();
+        assert!(signature::verify(b"secret", &received[0].body, sig));
+    }
+
+    #[test]
+    fn test_replay_actions() {
+        let opt = Options {
+            actions: vec!["closed".to_owned()],
+            ..Options::default()
+        };
+        let deliveries = vec![
+            delivery(Some("issues"), r#"{"action":"opened"}"#),
+            delivery(Some("issues"), r#"{"action":"closed"}"#),
+            delivery(Some("ping"), r#"{"zen":"z"}"#),
+        ];
+        let received = replay_locally(deliveries, &opt);
+        assert_eq!(bodies(&received), [r#"{"action":"closed"}"#]);
+    }
+
+    #[test]
+    fn test_replay_as_event() {
+        let opt = Options {
+            event: Some("ping".to_owned()),
+            events: vec!["ping".to_owned()],
+            ..Options::default()
+        };
+        let deliveries = vec![
+            delivery(None, r#"{"zen":"z"}"#),
+            delivery(Some("issues"), r#"{"action":"opened"}"#),
+        ];
+        let received = replay_locally(deliveries, &opt);
+        assert_eq!(bodies(&received), [r#"{"zen":"z"}"#]);
+        assert_eq!(received[0].event(), Some("ping"));
+        assert_eq!(received[0].header("Content-Type"), Some("application/json"));
+    }
+}
@@ -0,0 +1,73 @@
+//! `X-Hub-Signature-256` of deliveries, HMAC-SHA256 of the body keyed by the webhook secret
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+pub const HEADER: &str = "X-Hub-Signature-256";
+
+const PREFIX: &str = "sha256=";
+
+fn mac(secret: &[u8], body: &[u8]) -> Hmac<Sha256> {
+    // HMAC accepts keys of any length
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).unwrap();
+    mac.update(body);
+    mac
+}
+
+/// value of [`HEADER`] for `body`, e.g. `sha256=757107ea...`
+pub fn sign(secret: &[u8], body: &[u8]) -> String {
+    let digest = mac(secret, body).finalize().into_bytes();
+    let mut signature = String::with_capacity(PREFIX.len() + digest.len() * 2);
+    signature.push_str(PREFIX);
+    for b in digest {
+        signature.push_str(&format!("{b:02x}"));
+    }
+    signature
+}
+
+/// whether `signature` (value of [`HEADER`]) is of `body`. compares in constant time.
+pub fn verify(secret: &[u8], body: &[u8], signature: &str) -> bool {
+    let Some(hex) = signature.strip_prefix(PREFIX) else {
+        return false;
+    };
+    let Some(digest) = decode_hex(hex) else {
+        return false;
+    };
+    mac(secret, body).verify_slice(&digest).is_ok()
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if hex.len() % 2 != 0 {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signature() {
+        // https://docs.github.com/en/webhooks/using-webhooks/validating-webhook-deliveries#testing-the-webhook-payload-validation
+        let signature = sign(b"It's a Secret to Everybody", b"Hello, World!");
+        assert_eq!(
+            signature,
+            "sha256=757107ea0eb2509fc211221cce984b8a37570b6d7586c22c46f4379c8b043e17"
+        );
+        assert!(verify(
+            b"It's a Secret to Everybody",
+            b"Hello, World!",
+            &signature
+        ));
+        assert!(!verify(b"wrong", b"Hello, World!", &signature));
+        assert!(!verify(
+            b"It's a Secret to Everybody",
+            b"Hello, World!",
+            "sha256=zz"
+        ));
+    }
+}
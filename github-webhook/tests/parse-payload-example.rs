@@ -1,3 +1,6 @@
+// payload types of the event are only generated with its feature
+#![cfg(any(feature = "all-events", feature = "branch_protection_rule"))]
+
 //use std::assert_matches::assert_matches;
 use std::env;
 
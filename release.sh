@@ -1,5 +1,44 @@
 #!/bin/bash
+set -eo pipefail
+
+# refuse to publish if the payload types need a larger bump than planned.
+# types are compared by the IR kept at each release, so changes of the generator count too.
+ir_file=github-webhook/payload-types.ir.json
+prev_tag=$(git describe --tags --abbrev=0)
+octokit_version() {
+	sed -n 's/^version = "\(v.*\)"$/\1/p'
+}
+octokit=$(octokit_version <github-webhook/Cargo.toml)
+planned=$(sed -n '/^\[workspace.package\]/,/^\[/s/^version = "\(.*\)"$/\1/p' Cargo.toml)
+
+generator=(cargo run -q -p github-webhook-type-generator --)
+# assigned first, as a failing command substitution in arguments does not stop the script
+dts=$("${generator[@]}" download --version "$octokit")
+"${generator[@]}" ir "$dts" --output "$ir_file"
+if ! git ls-files --error-unmatch -- "$ir_file" >/dev/null 2>&1; then
+	# first release keeping the IR
+	echo "note: committing $ir_file, which later releases are compared by" >&2
+	git add -- "$ir_file"
+	git commit -q -m "Keep IR of the payload types" -- "$ir_file"
+elif [ -n "$(git status --porcelain -- "$ir_file")" ]; then
+	echo "error: $ir_file is updated, commit it before release" >&2
+	exit 1
+fi
+
+prev_ir=$(mktemp)
+trap 'rm -f "$prev_ir"' EXIT
+if ! git show "$prev_tag:$ir_file" >"$prev_ir" 2>/dev/null; then
+	# released before the IR was kept
+	echo "warning: $prev_tag has no $ir_file, regenerating it by the current generator" >&2
+	echo "warning: changes of the generator since $prev_tag are not checked in this release" >&2
+	prev_octokit=$(git show "$prev_tag:github-webhook/Cargo.toml" | octokit_version)
+	prev_dts=$("${generator[@]}" download --version "$prev_octokit")
+	"${generator[@]}" ir "$prev_dts" --output "$prev_ir"
+fi
+"${generator[@]}" semver --ir "$prev_ir" "$ir_file" \
+	--released "${prev_tag#v}" --planned "$planned"
 
-cargo publish -p github-webhook-type-generator
 cargo publish -p github-webhook-dts-downloader
+cargo publish -p github-webhook-type-generator
+cargo publish -p github-webhook-types-macro
 cargo publish -p github-webhook
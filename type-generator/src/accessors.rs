@@ -0,0 +1,515 @@
+//! traits to get fields shared by events (e.g. `HasRepository`) without matching each event,
+//! and methods of the root (`Schema`) returning them
+//!
+//! a field is shared if at least two events have it with the same type. events having the field
+//! with another type do not implement the trait.
+//!
+//! internally tagged enums get a fieldless enum of the tag values (e.g. `IssuesEventAction`) and
+//! a method returning it (e.g. `action()`), and the root gets `event_name()` and `action()`,
+//! and `deserialize_event()` choosing the variant by event name (and
+//! `deserialize_event_action()` also by action).
+
+use std::collections::{BTreeMap, HashSet};
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+use crate::{
+    case, ident,
+    ir::{
+        RustEnum, RustEnumMember, RustEnumMemberKind, RustFieldAttr, RustSegment, RustStruct,
+        RustStructAttr, RustType, RustVariantAttr, SerdeContainerAttr, SerdeFieldAttr,
+        SerdeVariantAttr,
+    },
+    split::event_name,
+    to_tokens::id,
+};
+
+/// items of the root generated by [`root_names`], which shared fields must not shadow
+const ROOT_ITEMS: &[&str] = &[
+    "action",
+    "event_name",
+    "deserialize_event",
+    "deserialize_event_action",
+    "EVENT_NAMES",
+];
+
+struct Field<'a> {
+    /// field name in Rust
+    name: &'a str,
+    ty: &'a RustType,
+    /// events having the field
+    events: Vec<&'a RustStruct>,
+}
+
+/// traits and their impls for events reachable from `root`, and methods of `root`
+pub fn accessors(segments: &[RustSegment], root: &str) -> TokenStream {
+    let Some(RustSegment::Enum(root)) = segments.iter().find(|s| s.name() == root) else {
+        return TokenStream::new();
+    };
+    let mut events = Vec::new();
+    let mut visited = HashSet::new();
+    for ty in variant_types(root) {
+        collect_events(segments, ty, &mut events, &mut visited);
+    }
+
+    // field name -> type name -> events
+    let mut candidates: BTreeMap<&str, Vec<Field>> = BTreeMap::new();
+    for e in &events {
+        for m in &e.member {
+            let RustType::Custom(tn) = &m.ty.ty else {
+                continue;
+            };
+            if m.attr
+                .as_inner()
+                .contains(&RustFieldAttr::Serde(SerdeFieldAttr::Flatten))
+                || ROOT_ITEMS.contains(&ident::unraw(&m.name))
+            {
+                continue;
+            }
+            let fields = candidates.entry(&m.name).or_default();
+            match fields
+                .iter_mut()
+                .find(|f| f.ty.as_custom().map(|t| &t.name) == Some(&tn.name))
+            {
+                Some(f) => f.events.push(e),
+                None => fields.push(Field {
+                    name: &m.name,
+                    ty: &m.ty.ty,
+                    events: vec![e],
+                }),
+            }
+        }
+    }
+
+    let mut tokens = TokenStream::new();
+    let mut methods = TokenStream::new();
+    for fields in candidates.into_values() {
+        // the type most events agree with, the first one on ties
+        let Some(field) = fields
+            .into_iter()
+            .rev()
+            .max_by_key(|f| f.events.len())
+            .filter(|f| f.events.len() >= 2)
+        else {
+            continue;
+        };
+        let (trait_tokens, method) = accessor(segments, root, &field);
+        tokens.extend(trait_tokens);
+        methods.extend(method);
+    }
+
+    for e in segments.iter().filter_map(|s| match s {
+        RustSegment::Enum(e) => Some(e),
+        _ => None,
+    }) {
+        tokens.extend(tag_enum(segments, e));
+    }
+    methods.extend(root_names(segments, root));
+
+    if !methods.is_empty() {
+        let root_ty = self_type(&root.name, root.is_borrowed);
+        let generics = generics(root.is_borrowed);
+        tokens.extend(quote! {
+            impl #generics #root_ty {
+                #methods
+            }
+        });
+    }
+    tokens
+}
+
+fn accessor(
+    segments: &[RustSegment],
+    root: &RustEnum,
+    field: &Field,
+) -> (TokenStream, TokenStream) {
+    let name = id!(field.name);
+    let ty = field.ty;
+    let borrowed = ty.as_custom().map_or(false, |t| t.is_borrowed);
+    let mut pascal = ident::unraw(field.name).to_owned();
+    case::detect_case(&pascal)
+        .into_rename_rule()
+        .convert_to_pascal(&mut pascal);
+    let trait_name = format_ident!("Has{}", pascal);
+    let trait_generics = generics(borrowed);
+    let doc = format!("types having `{}`", ident::unraw(field.name));
+    let mut tokens = quote! {
+        #[doc = #doc]
+        pub trait #trait_name #trait_generics {
+            fn #name(&self) -> Option<&#ty>;
+        }
+    };
+
+    // implementors by name, to delegate from enums
+    let mut implemented: HashSet<&str> = HashSet::new();
+    for e in &field.events {
+        let member = e.member.iter().find(|m| m.name == field.name).unwrap();
+        let body = if member.ty.is_optional || member.ty.is_nullable {
+            quote! { self.#name.as_ref() }
+        } else {
+            quote! { Some(&self.#name) }
+        };
+        let self_ty = self_type(&e.name, e.is_borrowed);
+        let generics = generics(e.is_borrowed);
+        tokens.extend(quote! {
+            impl #generics #trait_name #trait_generics for #self_ty {
+                fn #name(&self) -> Option<&#ty> {
+                    #body
+                }
+            }
+        });
+        implemented.insert(&e.name);
+    }
+
+    // enums of events, inner ones first
+    let mut enums = Vec::new();
+    let mut visited = HashSet::new();
+    for t in variant_types(root) {
+        collect_enums(segments, t, &mut enums, &mut visited);
+    }
+    for e in enums {
+        if !variant_types(e).any(|t| is_implemented(segments, t, &implemented)) {
+            continue;
+        }
+        let body = delegate(segments, e, &name, &implemented);
+        let self_ty = self_type(&e.name, e.is_borrowed);
+        let generics = generics(e.is_borrowed);
+        tokens.extend(quote! {
+            impl #generics #trait_name #trait_generics for #self_ty {
+                fn #name(&self) -> Option<&#ty> {
+                    #body
+                }
+            }
+        });
+        implemented.insert(&e.name);
+    }
+
+    let body = delegate(segments, root, &name, &implemented);
+    let method = quote! {
+        pub fn #name(&self) -> Option<&#ty> {
+            #body
+        }
+    };
+    (tokens, method)
+}
+
+/// match arms calling the accessor of variants implementing it
+fn delegate(
+    segments: &[RustSegment],
+    e: &RustEnum,
+    name: &proc_macro2::Ident,
+    implemented: &HashSet<&str>,
+) -> TokenStream {
+    let arms = e.member.iter().map(|m| match &m.kind {
+        RustEnumMemberKind::Nullary(v)
+        | RustEnumMemberKind::Discriminant {
+            variant_name: v, ..
+        } => {
+            let v = id!(v);
+            quote! { Self::#v => None, }
+        }
+        RustEnumMemberKind::Unary(t) | RustEnumMemberKind::UnaryNamed { type_name: t, .. } => {
+            let v = match &m.kind {
+                RustEnumMemberKind::UnaryNamed { variant_name, .. } => id!(variant_name),
+                _ => id!(t.to_ident()),
+            };
+            if is_implemented(segments, t, implemented) {
+                quote! { Self::#v(e) => e.#name(), }
+            } else {
+                quote! { Self::#v(_) => None, }
+            }
+        }
+    });
+    quote! {
+        match self {
+            #(#arms)*
+        }
+    }
+}
+
+fn is_implemented(segments: &[RustSegment], ty: &RustType, implemented: &HashSet<&str>) -> bool {
+    resolve(segments, ty).map_or(false, |s| implemented.contains(s.name()))
+}
+
+fn self_type(name: &str, is_borrowed: bool) -> TokenStream {
+    let name = id!(name);
+    if is_borrowed {
+        quote! { #name<'a> }
+    } else {
+        quote! { #name }
+    }
+}
+
+fn generics(is_borrowed: bool) -> TokenStream {
+    if is_borrowed {
+        quote! { <'a> }
+    } else {
+        TokenStream::new()
+    }
+}
+
+fn variant_types(e: &RustEnum) -> impl Iterator<Item = &RustType> {
+    e.member.iter().filter_map(|m| m.kind.as_type())
+}
+
+/// find the segment of the custom type, following aliases
+fn resolve<'a>(segments: &'a [RustSegment], ty: &RustType) -> Option<&'a RustSegment> {
+    let mut ty = ty;
+    for _ in 0..segments.len() {
+        let name = &ty.as_custom()?.name;
+        match segments.iter().find(|s| s.name() == name)? {
+            RustSegment::Alias(a) => ty = &a.ty,
+            s => return Some(s),
+        }
+    }
+    None
+}
+
+/// structs of events, looking into enums of events (e.g. `IssuesEvent`)
+fn collect_events<'a>(
+    segments: &'a [RustSegment],
+    ty: &RustType,
+    events: &mut Vec<&'a RustStruct>,
+    visited: &mut HashSet<&'a str>,
+) {
+    match resolve(segments, ty) {
+        Some(RustSegment::Struct(s)) if visited.insert(&s.name) => events.push(s),
+        Some(RustSegment::Enum(e)) if visited.insert(&e.name) => {
+            for t in variant_types(e) {
+                collect_events(segments, t, events, visited);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn collect_enums<'a>(
+    segments: &'a [RustSegment],
+    ty: &RustType,
+    enums: &mut Vec<&'a RustEnum>,
+    visited: &mut HashSet<&'a str>,
+) {
+    if let Some(RustSegment::Enum(e)) = resolve(segments, ty) {
+        if visited.insert(&e.name) {
+            for t in variant_types(e) {
+                collect_enums(segments, t, enums, visited);
+            }
+            enums.push(e);
+        }
+    }
+}
+
+fn tag(e: &RustEnum) -> Option<&str> {
+    e.attr.as_inner().iter().find_map(|a| match a {
+        RustStructAttr::Serde(SerdeContainerAttr::Tag(tag)) => Some(tag.as_str()),
+        _ => None,
+    })
+}
+
+fn variant_ident(m: &RustEnumMember) -> proc_macro2::Ident {
+    match &m.kind {
+        RustEnumMemberKind::Nullary(v)
+        | RustEnumMemberKind::UnaryNamed {
+            variant_name: v, ..
+        }
+        | RustEnumMemberKind::Discriminant {
+            variant_name: v, ..
+        } => id!(v),
+        RustEnumMemberKind::Unary(t) => id!(t.to_ident()),
+    }
+}
+
+/// value of the tag selecting the variant
+fn tag_value(e: &RustEnum, m: &RustEnumMember) -> String {
+    if let Some(renamed) = m.attr.as_inner().iter().find_map(|a| match a {
+        RustVariantAttr::Serde(SerdeVariantAttr::Rename(s)) => Some(s),
+        _ => None,
+    }) {
+        return renamed.to_owned();
+    }
+    let variant = variant_ident(m).to_string();
+    match e.attr.as_inner().iter().find_map(|a| match a {
+        RustStructAttr::Serde(SerdeContainerAttr::RenameAll(rr)) => Some(rr),
+        _ => None,
+    }) {
+        Some(rr) => rr.apply_to_variant(&variant),
+        None => variant,
+    }
+}
+
+/// name of the enum of the tag values, e.g. `IssuesEventAction`
+fn tag_enum_name(segments: &[RustSegment], e: &RustEnum, tag: &str) -> String {
+    let mut pascal = tag.to_owned();
+    case::detect_case(&pascal)
+        .into_rename_rule()
+        .convert_to_pascal(&mut pascal);
+    let mut name = format!("{}{}", e.name, ident::field_name(&pascal));
+    while segments.iter().any(|s| s.name() == name) {
+        name.push_str("Kind");
+    }
+    name
+}
+
+/// fieldless enum of the tag values and the method of `e` returning it
+fn tag_enum(segments: &[RustSegment], e: &RustEnum) -> TokenStream {
+    let Some(tag) = tag(e) else {
+        return TokenStream::new();
+    };
+    let name = id!(&tag_enum_name(segments, e, tag));
+    let method = id!(&ident::field_name(tag));
+    let variants: Vec<_> = e.member.iter().map(variant_ident).collect();
+    let values = e.member.iter().map(|m| tag_value(e, m));
+    let patterns = e.member.iter().map(|m| {
+        let v = variant_ident(m);
+        match m.kind {
+            RustEnumMemberKind::Nullary(_) | RustEnumMemberKind::Discriminant { .. } => {
+                quote! { Self::#v }
+            }
+            _ => quote! { Self::#v(_) },
+        }
+    });
+    let doc = format!("`{tag}` of [`{}`]", e.name);
+    let self_ty = self_type(&e.name, e.is_borrowed);
+    let generics = generics(e.is_borrowed);
+    quote! {
+        #[doc = #doc]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum #name {
+            #(#variants,)*
+        }
+        impl #name {
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    #(Self::#variants => #values,)*
+                }
+            }
+        }
+        impl std::fmt::Display for #name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+        impl #generics #self_ty {
+            pub fn #method(&self) -> #name {
+                match self {
+                    #(#patterns => #name::#variants,)*
+                }
+            }
+        }
+    }
+}
+
+/// `event_name()` and `action()` of the root
+fn root_names(segments: &[RustSegment], root: &RustEnum) -> TokenStream {
+    let mut event_arms = Vec::new();
+    let mut action_arms = Vec::new();
+    let mut deserialize_arms = Vec::new();
+    let mut action_deserialize_arms = Vec::new();
+    let mut event_names = Vec::new();
+    for m in &root.member {
+        let v = variant_ident(m);
+        let Some(ty) = m.kind.as_type() else {
+            let event = event_name(&v.to_string());
+            event_arms.push(quote! { Self::#v => #event, });
+            action_arms.push(quote! { Self::#v => None, });
+            continue;
+        };
+        let event = event_name(ty.to_ident());
+        event_arms.push(quote! { Self::#v(_) => #event, });
+        event_names.push(event.clone());
+        deserialize_arms.push(quote! {
+            #event => Self::#v(serde::Deserialize::deserialize(deserializer)?),
+        });
+        match resolve(segments, ty) {
+            Some(RustSegment::Enum(e)) if tag(e) == Some("action") => {
+                action_arms.push(quote! { Self::#v(e) => Some(e.action().as_str()), });
+                let name = id!(&e.name);
+                for am in e.member.iter().filter(|am| am.kind.as_type().is_some()) {
+                    let (av, action) = (variant_ident(am), tag_value(e, am));
+                    action_deserialize_arms.push(quote! {
+                        (#event, Some(#action)) => Self::#v(
+                            #name::#av(serde::Deserialize::deserialize(deserializer)?),
+                        ),
+                    });
+                }
+            }
+            _ => action_arms.push(quote! { Self::#v(_) => None, }),
+        }
+        action_deserialize_arms.push(quote! {
+            (#event, _) => Self::#v(serde::Deserialize::deserialize(deserializer)?),
+        });
+    }
+    // without events (e.g. all disabled), matching only `_` would make the rest unreachable
+    let (deserialize_body, action_deserialize_body) = if deserialize_arms.is_empty() {
+        (
+            quote! {
+                let _ = (event, deserializer);
+                Ok(None)
+            },
+            quote! {
+                let _ = (event, action, deserializer);
+                Ok(None)
+            },
+        )
+    } else {
+        (
+            quote! {
+                Ok(Some(match event {
+                    #(#deserialize_arms)*
+                    _ => return Ok(None),
+                }))
+            },
+            quote! {
+                Ok(Some(match (event, action) {
+                    #(#action_deserialize_arms)*
+                    _ => return Ok(None),
+                }))
+            },
+        )
+    };
+    // references to empty enums are not considered empty
+    let scrutinee = if root.member.is_empty() {
+        quote! { *self }
+    } else {
+        quote! { self }
+    };
+    let de_bound = if root.is_borrowed {
+        quote! { where 'de: 'a }
+    } else {
+        TokenStream::new()
+    };
+    quote! {
+        /// events [`Self::deserialize_event`] accepts
+        pub const EVENT_NAMES: &'static [&'static str] = &[#(#event_names),*];
+        /// deserialize the payload of `event` (as in `X-GitHub-Event` header) into its variant,
+        /// which reports errors better than trying every variant. `None` for unknown events.
+        pub fn deserialize_event<'de, D: serde::Deserializer<'de>>(
+            event: &str,
+            deserializer: D,
+        ) -> Result<Option<Self>, D::Error> #de_bound {
+            #deserialize_body
+        }
+        /// like [`Self::deserialize_event`], but deserializes the variant of `action` directly
+        /// for events tagged by it, so that errors are not hidden by the buffering of the tag
+        pub fn deserialize_event_action<'de, D: serde::Deserializer<'de>>(
+            event: &str,
+            action: Option<&str>,
+            deserializer: D,
+        ) -> Result<Option<Self>, D::Error> #de_bound {
+            #action_deserialize_body
+        }
+        /// name of the event as in `X-GitHub-Event` header, e.g. `pull_request`
+        pub fn event_name(&self) -> &'static str {
+            match #scrutinee {
+                #(#event_arms)*
+            }
+        }
+        /// `action` of the event, if the event has actions
+        pub fn action(&self) -> Option<&'static str> {
+            match #scrutinee {
+                #(#action_arms)*
+            }
+        }
+    }
+}
@@ -16,6 +16,10 @@ impl<Node: Copy + Hash + Eq + std::fmt::Debug> DirectedAcyclicGraph<Node> {
         }
     }
 
+    fn add_node(&mut self, node: Node) {
+        self.nodes.insert(node);
+    }
+
     fn add_edge(&mut self, from: Node, to: Node) {
         self.nodes.insert(from);
         self.nodes.insert(to);
@@ -122,6 +126,10 @@ impl<Node: Copy + Hash + Eq + std::fmt::Debug> CoDirectedAcyclicGraph<Node> {
         }
     }
 
+    pub fn add_node(&mut self, node: Node) {
+        self.dag.add_node(node);
+    }
+
     pub fn add_edge(&mut self, from: Node, to: Node) {
         self.dag.add_edge(to, from);
     }
@@ -129,6 +137,26 @@ impl<Node: Copy + Hash + Eq + std::fmt::Debug> CoDirectedAcyclicGraph<Node> {
     pub fn co_topo_sort(&self) -> Result<Vec<Node>, Vec<Node>> {
         self.dag.topo_sort()
     }
+
+    /// nodes reachable from `node` by the added edges, including `node` itself
+    pub fn reachable(&self, node: Node) -> HashSet<Node> {
+        let mut forward: HashMap<Node, Vec<Node>> = HashMap::new();
+        for (to, froms) in &self.dag.edges {
+            for from in froms {
+                forward.entry(*from).or_default().push(*to);
+            }
+        }
+        let mut reachable = HashSet::from([node]);
+        let mut stack = vec![node];
+        while let Some(n) = stack.pop() {
+            for child in forward.get(&n).into_iter().flatten() {
+                if reachable.insert(*child) {
+                    stack.push(*child);
+                }
+            }
+        }
+        reachable
+    }
 }
 
 impl<Node: Copy + Hash + Eq + std::fmt::Debug> Default for CoDirectedAcyclicGraph<Node> {
@@ -169,6 +197,30 @@ mod tests {
         assert_eq!(topo_order, vec!["E", "D", "C", "B", "A"]);
     }
 
+    #[test]
+    fn test_reachable() {
+        let mut dag = CoDirectedAcyclicGraph::new();
+        dag.add_edge("A", "B");
+        dag.add_edge("B", "C");
+        dag.add_edge("D", "C");
+
+        let mut reachable: Vec<_> = dag.reachable("A").into_iter().collect();
+        reachable.sort();
+        assert_eq!(reachable, vec!["A", "B", "C"]);
+        assert_eq!(dag.reachable("C"), HashSet::from(["C"]));
+    }
+
+    #[test]
+    fn test_isolated_node() {
+        let mut dag = CoDirectedAcyclicGraph::new();
+        dag.add_node("A");
+        dag.add_edge("B", "C");
+
+        let mut topo_order = dag.co_topo_sort().unwrap();
+        topo_order.sort();
+        assert_eq!(topo_order, vec!["A", "B", "C"]);
+    }
+
     #[test]
     fn test_find_cycle() {
         let mut graph = DirectedAcyclicGraph::new();
@@ -0,0 +1,344 @@
+//! changes of generated types between two schemas, e.g. two versions of octokit/webhooks
+//!
+//! types, fields and variants are compared by their generated Rust names, so run the same
+//! transformers (and overrides) on both sides.
+
+use std::{collections::BTreeMap, fmt};
+
+use quote::ToTokens;
+
+use crate::ir::{RustEnum, RustSegment, RustStruct};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Item {
+    Type,
+    Field,
+    Variant,
+}
+
+impl Item {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Item::Type => "type",
+            Item::Field => "field",
+            Item::Variant => "variant",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    /// signature changed, e.g. type of field or its optionality
+    Changed {
+        old: String,
+        new: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change {
+    pub item: Item,
+    /// `Type`, `Type.field` or `Type::Variant`
+    pub path: String,
+    pub kind: ChangeKind,
+}
+
+impl Change {
+    /// version bump the change requires under Rust semver rules.
+    ///
+    /// generated structs have only public fields and enums are exhaustive, so new fields
+    /// break struct literals and patterns, and new variants break matches.
+    pub fn bump(&self) -> Bump {
+        match (&self.kind, self.item) {
+            (ChangeKind::Added, Item::Type) => Bump::Minor,
+            _ => Bump::Major,
+        }
+    }
+
+    /// whether code using the old types may fail to compile with the new ones
+    pub fn is_breaking(&self) -> bool {
+        self.bump() == Bump::Major
+    }
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self { item, path, kind } = self;
+        let item = item.as_str();
+        match kind {
+            ChangeKind::Added => write!(f, "+ {item} {path}")?,
+            ChangeKind::Removed => write!(f, "- {item} {path}")?,
+            ChangeKind::Changed { old, new } => write!(f, "~ {item} {path}: {old} -> {new}")?,
+        }
+        if self.is_breaking() {
+            write!(f, " (breaking)")?;
+        }
+        Ok(())
+    }
+}
+
+/// part of `major.minor.patch` to increment
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Bump {
+    Patch,
+    Minor,
+    Major,
+}
+
+impl Bump {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Bump::Patch => "patch",
+            Bump::Minor => "minor",
+            Bump::Major => "major",
+        }
+    }
+
+    /// version following `version` (`major.minor.patch`) with the bump.
+    ///
+    /// as in Cargo, `0.x` versions bump the minor for breaking changes and the patch otherwise.
+    pub fn apply(self, version: &str) -> Option<String> {
+        let [major, minor, patch] = parse_version(version)?;
+        let bump = match (major, self) {
+            (0, Bump::Major) => Bump::Minor,
+            (0, _) => Bump::Patch,
+            _ => self,
+        };
+        let [major, minor, patch] = match bump {
+            Bump::Major => [major + 1, 0, 0],
+            Bump::Minor => [major, minor + 1, 0],
+            Bump::Patch => [major, minor, patch + 1],
+        };
+        Some(format!("{major}.{minor}.{patch}"))
+    }
+}
+
+/// the largest bump `changes` require, i.e. patch if nothing changed
+pub fn required_bump(changes: &[Change]) -> Bump {
+    changes
+        .iter()
+        .map(Change::bump)
+        .max()
+        .unwrap_or(Bump::Patch)
+}
+
+/// whether `planned` is at least the version `released` requires after `changes`
+pub fn is_compatible(released: &str, planned: &str, changes: &[Change]) -> Option<bool> {
+    let required = parse_version(&required_bump(changes).apply(released)?)?;
+    Some(parse_version(planned)? >= required)
+}
+
+/// `1.2.3` -> `[1, 2, 3]`, ignoring pre-release and build metadata
+fn parse_version(version: &str) -> Option<[u64; 3]> {
+    let version = version.trim_start_matches('v');
+    let core = version.split(['-', '+']).next()?;
+    let mut parts = core.split('.').map(|p| p.parse().ok());
+    let parsed = [parts.next()??, parts.next()??, parts.next()??];
+    parts.next().is_none().then_some(parsed)
+}
+
+/// changes from `old` to `new`, ordered by type name
+pub fn diff(old: &[RustSegment], new: &[RustSegment]) -> Vec<Change> {
+    let old: BTreeMap<&str, &RustSegment> = old.iter().map(|s| (s.name(), s)).collect();
+    let new: BTreeMap<&str, &RustSegment> = new.iter().map(|s| (s.name(), s)).collect();
+    let mut names: Vec<&str> = old.keys().chain(new.keys()).copied().collect();
+    names.sort_unstable();
+    names.dedup();
+
+    let mut changes = Vec::new();
+    for name in names {
+        let change = |kind| Change {
+            item: Item::Type,
+            path: name.to_owned(),
+            kind,
+        };
+        match (old.get(name), new.get(name)) {
+            (Some(_), None) => changes.push(change(ChangeKind::Removed)),
+            (None, Some(_)) => changes.push(change(ChangeKind::Added)),
+            (Some(old), Some(new)) => {
+                let (old_sig, new_sig) = (signature(old), signature(new));
+                if old_sig != new_sig {
+                    changes.push(change(ChangeKind::Changed {
+                        old: old_sig,
+                        new: new_sig,
+                    }));
+                }
+                match (old, new) {
+                    (RustSegment::Struct(o), RustSegment::Struct(n)) => {
+                        diff_members(Item::Field, ".", name, fields(o), fields(n), &mut changes)
+                    }
+                    (RustSegment::Enum(o), RustSegment::Enum(n)) => diff_members(
+                        Item::Variant,
+                        "::",
+                        name,
+                        variants(o),
+                        variants(n),
+                        &mut changes,
+                    ),
+                    _ => (),
+                }
+            }
+            (None, None) => unreachable!(),
+        }
+    }
+    changes
+}
+
+/// kind and generics, with the aliased type for aliases
+fn signature(segment: &RustSegment) -> String {
+    let (kind, is_borrowed) = match segment {
+        RustSegment::Struct(s) => ("struct", s.is_borrowed),
+        RustSegment::Enum(e) => ("enum", e.is_borrowed),
+        RustSegment::Alias(a) => {
+            return format!(
+                "type{} = {}",
+                if a.is_borrowed { "<'a>" } else { "" },
+                rust_type(&a.ty)
+            )
+        }
+        RustSegment::Const(_) => return "const".to_owned(),
+    };
+    format!("{kind}{}", if is_borrowed { "<'a>" } else { "" })
+}
+
+/// `Option<&'a str>` rather than `Option < & 'a str >`
+fn rust_type(ty: &impl ToTokens) -> String {
+    let mut s = ty.to_token_stream().to_string();
+    for (from, to) in [(" < ", "<"), (" >", ">"), ("& '", "&'"), (" ,", ",")] {
+        s = s.replace(from, to);
+    }
+    s
+}
+
+fn diff_members(
+    item: Item,
+    sep: &str,
+    type_name: &str,
+    old: Vec<(&str, String)>,
+    new: Vec<(&str, String)>,
+    changes: &mut Vec<Change>,
+) {
+    let old: BTreeMap<&str, String> = old.into_iter().collect();
+    let mut new: BTreeMap<&str, String> = new.into_iter().collect();
+    for (name, old_ty) in old {
+        let path = format!("{type_name}{sep}{name}");
+        let kind = match new.remove(name) {
+            None => ChangeKind::Removed,
+            Some(new_ty) if new_ty != old_ty => ChangeKind::Changed {
+                old: old_ty,
+                new: new_ty,
+            },
+            Some(_) => continue,
+        };
+        changes.push(Change { item, path, kind });
+    }
+    for name in new.into_keys() {
+        changes.push(Change {
+            item,
+            path: format!("{type_name}{sep}{name}"),
+            kind: ChangeKind::Added,
+        });
+    }
+}
+
+/// generated fields and their types. the others (e.g. consumed tags) are not part of the API.
+fn fields(s: &RustStruct) -> Vec<(&str, String)> {
+    s.member
+        .iter()
+        .filter(|m| !m.ty.is_unknown())
+        .map(|m| (m.name.as_str(), rust_type(&m.ty)))
+        .collect()
+}
+
+/// variants and their payloads
+fn variants(e: &RustEnum) -> Vec<(&str, String)> {
+    e.member
+        .iter()
+        .map(|m| {
+            let payload = m
+                .kind
+                .as_type()
+                .map(|t| format!("({})", rust_type(t)))
+                .unwrap_or_default();
+            (m.kind.variant_name(), payload)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ir::{
+        RustContainerAttrs, RustEnumMemberKind, RustFieldAttrs, RustMemberType, RustStructMember,
+        RustType,
+    };
+
+    fn event(fields: &[(&str, bool)], actions: &[&str]) -> Vec<RustSegment> {
+        vec![
+            RustSegment::Struct(RustStruct::from_members(
+                "Event".to_owned(),
+                None,
+                fields.iter().map(|(name, is_optional)| RustStructMember {
+                    attr: RustFieldAttrs::new(),
+                    name: name.to_string(),
+                    ty: RustMemberType {
+                        ty: RustType::Number,
+                        is_optional: *is_optional,
+                        is_nullable: false,
+                    },
+                    comment: None,
+                }),
+            )),
+            RustSegment::Enum(RustEnum {
+                attr: RustContainerAttrs::new(),
+                name: "Action".to_owned(),
+                comment: None,
+                is_borrowed: false,
+                member: actions
+                    .iter()
+                    .map(|a| RustEnumMemberKind::Nullary(a.to_string()).into())
+                    .collect(),
+            }),
+        ]
+    }
+
+    #[test]
+    fn test_diff() {
+        let old = event(&[("id", false), ("size", false)], &["Created", "Deleted"]);
+        let new = event(&[("id", true), ("url", false)], &["Created", "Edited"]);
+        let changes: Vec<String> = diff(&old, &new).iter().map(|c| c.to_string()).collect();
+        assert_eq!(
+            changes,
+            [
+                "- variant Action::Deleted (breaking)",
+                "+ variant Action::Edited (breaking)",
+                "~ field Event.id: usize -> Option<usize> (breaking)",
+                "- field Event.size (breaking)",
+                "+ field Event.url (breaking)",
+            ]
+        );
+        assert!(diff(&old, &old).is_empty());
+    }
+
+    #[test]
+    fn test_bump() {
+        let old = event(&[("id", false)], &[]);
+        let mut new = event(&[("id", false)], &[]);
+        assert_eq!(required_bump(&diff(&old, &new)), Bump::Patch);
+        new.push(RustSegment::Struct(RustStruct::from_members(
+            "User".to_owned(),
+            None,
+            std::iter::empty(),
+        )));
+        let changes = diff(&old, &new);
+        assert_eq!(required_bump(&changes), Bump::Minor);
+        assert_eq!(Bump::Minor.apply("1.2.3").as_deref(), Some("1.3.0"));
+        assert_eq!(Bump::Minor.apply("0.6.0").as_deref(), Some("0.6.1"));
+        assert_eq!(Bump::Major.apply("0.6.1").as_deref(), Some("0.7.0"));
+        assert_eq!(is_compatible("1.2.3", "1.2.4", &changes), Some(false));
+        assert_eq!(is_compatible("1.2.3", "2.0.0", &changes), Some(true));
+        assert_eq!(is_compatible("1.2", "2.0.0", &changes), None);
+    }
+}
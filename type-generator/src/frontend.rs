@@ -1,17 +1,20 @@
+pub mod json_schema;
 pub mod merge_union_type_lits;
 pub mod name_types;
+pub mod openapi;
+pub mod utility_types;
 
-use once_cell::sync::Lazy;
 use std::{borrow::Cow, collections::HashMap};
 
 use crate::{
     case,
     frontend::merge_union_type_lits::Merged,
+    ident,
     ir::{
-        LiteralKeyMap, RustAlias, RustComment, RustContainerAttrs, RustEnum, RustEnumMember,
-        RustEnumMemberKind, RustFieldAttr, RustFieldAttrs, RustMemberType, RustSegment, RustStruct,
-        RustStructAttr, RustStructMember, RustType, RustVariantAttrs, SerdeContainerAttr,
-        SerdeFieldAttr, TypeName,
+        LiteralKeyMap, LiteralValue, Origins, RustAlias, RustComment, RustContainerAttrs, RustEnum,
+        RustEnumMember, RustEnumMemberKind, RustFieldAttr, RustFieldAttrs, RustMemberType,
+        RustSegment, RustStruct, RustStructAttr, RustStructMember, RustType, RustVariantAttrs,
+        SerdeContainerAttr, SerdeFieldAttr, TypeName,
     },
 };
 
@@ -42,6 +45,11 @@ pub fn interface2struct<'input>(
     st.segments.push(RustSegment::Struct(s));
 }
 
+/// `Some` if `n` is exactly an integer
+pub fn as_integer(n: f64) -> Option<i64> {
+    (n.fract() == 0.0 && n.abs() < i64::MAX as f64).then_some(n as i64)
+}
+
 pub fn ts_prop_signature<'input>(
     prop: &'input swc_ecma_ast::TsPropertySignature,
     st: &mut FrontendState<'input, '_>,
@@ -50,52 +58,51 @@ pub fn ts_prop_signature<'input>(
     lkm: &mut HashMap<String, HashMap<String, String>>,
 ) -> RustStructMember {
     let comment = st.get_comment(prop.span.lo);
-    let mut is_optional = prop.optional;
-    let mut pkey: &str = match &*prop.key {
+    let is_optional = prop.optional;
+    let pkey: &str = match &*prop.key {
         swc_ecma_ast::Expr::Ident(pkey) => &pkey.sym,
         swc_ecma_ast::Expr::Lit(swc_ecma_ast::Lit::Str(k)) => &k.value,
         _ => unreachable!(),
     };
-    let mut attr = RustFieldAttrs::new();
-    // avoid conflict to Rust reserved word
-    static RENAME_RULES: Lazy<HashMap<&str, &str>> = Lazy::new(|| {
-        HashMap::from_iter([
-            ("type", "type_"),
-            ("ref", "ref_"),
-            ("self", "self_"),
-            ("+1", "plus_1"),
-            ("-1", "minus_1"),
-        ])
-    });
-    if let Some(renamed) = RENAME_RULES.get(pkey) {
-        attr.add_attr(RustFieldAttr::Serde(SerdeFieldAttr::Rename(
-            pkey.to_owned(),
-        )));
-        pkey = renamed;
-    }
+    let (pname, attr) = rename_field(pkey);
     let ptype = &prop.type_ann.as_ref().unwrap().type_ann;
     let mut ctxt = ctxt.clone();
-    ctxt.projection(Cow::Borrowed(pkey));
+    ctxt.projection(Cow::Owned(ident::unraw(&pname).to_owned()));
 
-    let (is_optional2, ty) = ts_type_to_rs(st, &mut Some(ctxt), ptype, None, lkm);
-    is_optional |= is_optional2;
+    let (is_nullable, mut ty) = ts_type_to_rs(st, &mut Some(ctxt), ptype, None, lkm);
 
     fn extract_literal_type(ptype: &swc_ecma_ast::TsType) -> Option<&str> {
         ptype.as_ts_lit_type()?.lit.as_str()?.raw.as_deref()
     }
     if let Some(lit) = extract_literal_type(ptype) {
-        lkm.entry(name.to_owned()).or_default().insert(
-            pkey.to_owned(),
-            lit.strip_prefix('\"')
-                .unwrap()
-                .strip_suffix('\"')
-                .unwrap()
-                .to_owned(),
-        );
+        let lit = lit
+            .strip_prefix('\"')
+            .unwrap()
+            .strip_suffix('\"')
+            .unwrap()
+            .to_owned();
+        ty = RustType::Literal(LiteralValue::String(lit.clone()));
+        lkm.entry(name.to_owned())
+            .or_default()
+            .insert(pkey.to_owned(), lit);
+    } else if let Some(lit) = ptype.as_ts_lit_type() {
+        match &lit.lit {
+            swc_ecma_ast::TsLit::Bool(b) => ty = RustType::Literal(LiteralValue::Boolean(b.value)),
+            swc_ecma_ast::TsLit::Number(n) => {
+                if let Some(n) = as_integer(n.value) {
+                    ty = RustType::Literal(LiteralValue::Number(n));
+                }
+            }
+            _ => (),
+        }
     }
     RustStructMember {
-        ty: RustMemberType { ty, is_optional },
-        name: pkey.to_string(),
+        ty: RustMemberType {
+            ty,
+            is_optional,
+            is_nullable,
+        },
+        name: pname,
         attr,
         comment,
     }
@@ -112,6 +119,18 @@ pub fn ts_prop_signature<'input>(
     //};
 }
 
+/// valid Rust identifier for the property key, with `#[serde(rename)]` if needed
+pub fn rename_field(pkey: &str) -> (String, RustFieldAttrs) {
+    let name = ident::field_name(pkey);
+    let mut attr = RustFieldAttrs::new();
+    if ident::unraw(&name) != pkey {
+        attr.add_attr(RustFieldAttr::Serde(SerdeFieldAttr::Rename(
+            pkey.to_owned(),
+        )));
+    }
+    (name, attr)
+}
+
 pub fn ts_index_signature<'input>(
     index: &'input swc_ecma_ast::TsIndexSignature,
     comment: Option<RustComment>,
@@ -141,6 +160,7 @@ pub fn ts_index_signature<'input>(
         ty: RustMemberType {
             ty: RustType::Map(Box::new(key_ty), Box::new(value_ty)),
             is_optional: false,
+            is_nullable: false,
         },
         name: ident.sym.to_string(),
         attr: RustFieldAttrs::from_attr(RustFieldAttr::Serde(SerdeFieldAttr::Flatten)),
@@ -217,6 +237,37 @@ fn union_or_intersection<'input>(
                 //TODO: comment strs  // {:?}", strs));
             }
 
+            // numbers check: 0 | 1 | 2
+            if let Some(variants) = types
+                .iter()
+                .map(|t| match t.as_ts_lit_type()?.lit {
+                    swc_ecma_ast::TsLit::Number(ref n) => Some(n.value),
+                    _ => None,
+                })
+                .collect::<Option<Vec<f64>>>()
+            {
+                let Some(variants) = variants
+                    .into_iter()
+                    .map(as_integer)
+                    .collect::<Option<Vec<i64>>>()
+                else {
+                    return RustType::Float;
+                };
+                let ct = ctxt.as_mut().expect("provide ctxt");
+                let tn = name_types::numeric_literal_union(st, variants, comment, ct);
+                return RustType::Custom(tn);
+            }
+
+            // booleans check: true | false
+            if types.iter().all(|t| {
+                matches!(
+                    t.as_ts_lit_type().map(|l| &l.lit),
+                    Some(swc_ecma_ast::TsLit::Bool(_))
+                )
+            }) {
+                return RustType::Boolean;
+            }
+
             if types.len() >= 2 {
                 if let Some(variants) = types
                     .iter()
@@ -238,8 +289,9 @@ fn union_or_intersection<'input>(
                                 .into()
                         })
                         .collect();
+                    let name = st.create_ident_with(ctxt, Some(vec!["DistinctUnion".to_string()]));
                     let ty = st.push_segment(RustSegment::Enum(RustEnum {
-                        name: ctxt.create_ident_with(Some(vec!["DistinctUnion".to_string()])),
+                        name,
                         attr: RustContainerAttrs::from_attr(RustStructAttr::Serde(
                             SerdeContainerAttr::Untagged,
                         )),
@@ -251,6 +303,7 @@ fn union_or_intersection<'input>(
                         ty: RustMemberType {
                             ty,
                             is_optional: false,
+                            is_nullable: false,
                         },
                         name: "distinct".to_owned(),
                         attr: RustFieldAttrs::from_attr(RustFieldAttr::Serde(
@@ -263,9 +316,9 @@ fn union_or_intersection<'input>(
             }
 
             let type_convert_context = ctxt.as_mut().unwrap();
-            let mut name = type_convert_context.create_ident();
+            let mut name = st.create_ident(type_convert_context);
             if !type_convert_context.from_alias {
-                name.push_str("Union");
+                st.suffix_ident(&mut name, "Union");
             }
             let variants: Vec<_> = types
                 .iter()
@@ -290,65 +343,281 @@ fn union_or_intersection<'input>(
             RustType::Custom(TypeName::new(name))
         }
         TsUnionOrIntersectionType::TsIntersectionType(tints) => {
-            if tints.types.len() == 2 {
-                let mut iter = tints.types.iter();
-                // if types consist of type literal and type ref, create new struct with serde flatten attribute
-                let tref = iter.next().unwrap().as_ts_type_ref();
-                let tlit = iter.next().unwrap().as_ts_type_lit();
-                if let (Some(tref), Some(tlit)) = (tref, tlit) {
-                    let name = tref.type_name.as_ident().unwrap().sym.as_ref();
-                    let mut str = name_types::type_literal(
-                        st,
-                        tlit.members.iter(),
-                        None,
-                        &mut ctxt.unwrap(),
-                        lkm,
-                    );
-
-                    if str.member.iter().all(|m| m.ty.is_unknown()) {
-                        let struct_name = str.name.to_owned();
-                        let a = RustAlias {
-                            name: struct_name.to_owned(),
-                            is_borrowed: false,
-                            comment: None,
-                            ty: RustType::Custom(TypeName::new(name.to_owned())),
-                        };
-                        st.segments.push(RustSegment::Alias(a));
-                        return RustType::Custom(TypeName::new(struct_name));
-                    } else {
-                        // add flatten attributed field to struct
-                        let mut field_name = name.to_owned();
-                        case::CaseConvention::Pascal
-                            .into_rename_rule()
-                            .convert_to_snake(&mut field_name);
-                        str.member.push(RustStructMember {
-                            attr: RustFieldAttrs::from_attr(RustFieldAttr::Serde(
-                                SerdeFieldAttr::Flatten,
-                            )),
-                            name: field_name,
-                            ty: RustMemberType {
-                                is_optional: false,
-                                ty: RustType::Custom(TypeName::new(name.to_owned())),
-                            },
-                            comment,
-                        });
-                        let struct_name = str.name.to_owned();
-                        st.segments.push(RustSegment::Struct(str));
-                        return RustType::Custom(TypeName::new(struct_name));
+            let mut ctxt = ctxt.expect("provide ctxt");
+            intersection(st, &mut ctxt, tints, comment, lkm)
+        }
+    }
+}
+
+/// collect parts of intersection, flattening nested intersections like `A & (B & C)`
+fn intersection_parts<'input>(
+    types: &'input [Box<swc_ecma_ast::TsType>],
+    parts: &mut Vec<&'input swc_ecma_ast::TsType>,
+) {
+    use swc_ecma_ast::{TsType, TsUnionOrIntersectionType};
+    for t in types {
+        let mut t = &**t;
+        while let TsType::TsParenthesizedType(p) = t {
+            t = &p.type_ann;
+        }
+        match t {
+            TsType::TsUnionOrIntersectionType(TsUnionOrIntersectionType::TsIntersectionType(
+                tints,
+            )) => intersection_parts(&tints.types, parts),
+            _ => parts.push(t),
+        }
+    }
+}
+
+/// lower `A & B & { .. }` into one struct.
+///
+/// members of type literals are merged into the struct (a later declaration of the same key wins),
+/// and each other part becomes a field attributed `#[serde(flatten)]`.
+/// structs consisting only of flatten fields of plain structs are merged later by
+/// [`crate::transformer::merge_intersection`].
+fn intersection<'input>(
+    st: &mut FrontendState<'input, '_>,
+    ctxt: &mut TypeConvertContext<'input>,
+    tints: &'input swc_ecma_ast::TsIntersectionType,
+    comment: Option<RustComment>,
+    lkm: &mut LiteralKeyMap,
+) -> RustType {
+    let mut parts = Vec::new();
+    intersection_parts(&tints.types, &mut parts);
+
+    let name = st.create_ident(ctxt);
+    let mut member: Vec<RustStructMember> = Vec::new();
+    let mut flatten: Vec<RustType> = Vec::new();
+    for part in parts {
+        if let Some(tlit) = part.as_ts_type_lit() {
+            for m in &tlit.members {
+                let m = match m {
+                    swc_ecma_ast::TsTypeElement::TsPropertySignature(p) => {
+                        ts_prop_signature(p, st, ctxt, &name, lkm)
+                    }
+                    swc_ecma_ast::TsTypeElement::TsIndexSignature(i) => {
+                        ts_index_signature(i, None, st, ctxt, lkm)
+                    }
+                    _ => continue,
+                };
+                member.retain(|o| o.name != m.name);
+                member.push(m);
+            }
+            continue;
+        }
+        let (_, ty) = ts_type_to_rs(st, &mut Some(ctxt.clone()), part, None, lkm);
+        match ty {
+            RustType::Unknown
+            | RustType::UnknownLiteral
+            | RustType::UnknownIntersection
+            | RustType::Unit => (),
+            ty => flatten.push(ty),
+        }
+    }
+
+    if flatten.is_empty() && member.is_empty() {
+        return RustType::UnknownIntersection;
+    }
+    if flatten.len() == 1 && member.iter().all(|m| m.ty.is_unknown()) {
+        st.segments.push(RustSegment::Alias(RustAlias {
+            name: name.to_owned(),
+            is_borrowed: false,
+            comment: None,
+            ty: flatten.pop().unwrap(),
+        }));
+        return RustType::Custom(TypeName::new(name));
+    }
+
+    for ty in flatten {
+        let mut base = ty.to_ident().to_owned();
+        case::CaseConvention::Pascal
+            .into_rename_rule()
+            .convert_to_snake(&mut base);
+        let mut field_name = base.clone();
+        let mut n = 1;
+        while member.iter().any(|m| m.name == field_name) {
+            n += 1;
+            field_name = format!("{base}_{n}");
+        }
+        member.push(RustStructMember {
+            attr: RustFieldAttrs::from_attr(RustFieldAttr::Serde(SerdeFieldAttr::Flatten)),
+            name: field_name,
+            ty: RustMemberType {
+                is_optional: false,
+                is_nullable: false,
+                ty,
+            },
+            comment: None,
+        });
+    }
+    st.push_segment(RustSegment::Struct(RustStruct::from_members(
+        name,
+        comment,
+        member.into_iter(),
+    )))
+}
+
+/// `[A, B]` becomes `(A, B)`.
+/// as serde requires exact length for tuples, a tuple with optional (`[A, B?]`)
+/// or rest (`[A, ...B[]]`) elements becomes `Vec<A>` if every element has the same type,
+/// otherwise `Vec<Value>`.
+fn tuple_type<'input>(
+    st: &mut FrontendState<'input, '_>,
+    ctxt: &mut Option<TypeConvertContext<'input>>,
+    t: &'input swc_ecma_ast::TsTupleType,
+    lkm: &mut LiteralKeyMap,
+) -> RustType {
+    use swc_ecma_ast::TsType;
+    let mut is_variadic = false;
+    let elems: Vec<RustType> = t
+        .elem_types
+        .iter()
+        .map(|e| {
+            // labeled elements carry the modifier on label, e.g. `flag?: boolean`
+            let is_rest = matches!(e.label, Some(swc_ecma_ast::Pat::Rest(_)));
+            if let Some(swc_ecma_ast::Pat::Ident(i)) = &e.label {
+                is_variadic |= i.id.optional;
+            }
+            let ty = match &*e.ty {
+                TsType::TsArrayType(a) if is_rest => {
+                    is_variadic = true;
+                    &*a.elem_type
+                }
+                TsType::TsOptionalType(o) => {
+                    is_variadic = true;
+                    &*o.type_ann
+                }
+                TsType::TsRestType(r) => {
+                    is_variadic = true;
+                    match &*r.type_ann {
+                        TsType::TsArrayType(a) => &*a.elem_type,
+                        t => t,
                     }
                 }
+                t => t,
+            };
+            let (nullable, ty) = ts_type_to_rs(st, ctxt, ty, None, lkm);
+            if nullable {
+                // `Option` is not expressible in the IR except for members
+                RustType::Unknown
+            } else {
+                ty
             }
-            // dbg!(tints);
-            //todo!();
-            RustType::UnknownIntersection
+        })
+        .collect();
+    if !is_variadic {
+        return RustType::Tuple(elems);
+    }
+    let tokens = |t: &RustType| quote::quote!(#t).to_string();
+    let first = tokens(&elems[0]);
+    let elem = if elems.iter().all(|e| tokens(e) == first) {
+        elems.into_iter().next().unwrap()
+    } else {
+        RustType::Unknown
+    };
+    RustType::Array(Box::new(elem))
+}
+
+/// name of referred type. qualified names like `A.B` are referred by the last identifier
+pub fn type_ref_name(name: &swc_ecma_ast::TsEntityName) -> &str {
+    match name {
+        swc_ecma_ast::TsEntityName::Ident(i) => &i.sym,
+        swc_ecma_ast::TsEntityName::TsQualifiedName(q) => &q.right.sym,
+    }
+}
+
+/// type reference, understanding common utility generics
+fn type_ref<'input>(
+    st: &mut FrontendState<'input, '_>,
+    ctxt: &mut Option<TypeConvertContext<'input>>,
+    tref: &'input swc_ecma_ast::TsTypeRef,
+    comment: Option<RustComment>,
+    lkm: &mut LiteralKeyMap,
+) -> RustType {
+    use utility_types::Derivation;
+
+    let name = type_ref_name(&tref.type_name);
+    let params: Vec<&swc_ecma_ast::TsType> = tref
+        .type_params
+        .iter()
+        .flat_map(|p| p.params.iter().map(|t| &**t))
+        .collect();
+    if matches!(name, "Record" | "Array" | "ReadonlyArray") {
+        // the granted name is for the alias itself, not for the element
+        if let Some(ctxt) = ctxt {
+            ctxt.granted_name = None;
         }
     }
+    let derivation = match (name, params.as_slice()) {
+        ("Record", [key, value]) => {
+            // map keys must be hashable, which generated literal enums are not
+            let key = match key.as_ts_keyword_type() {
+                Some(k) => ts_keyword_type_to_rs(k),
+                None => RustType::String { is_borrowed: false },
+            };
+            let (_, value) = ts_type_to_rs(st, ctxt, value, None, lkm);
+            return RustType::Map(Box::new(key), Box::new(value));
+        }
+        ("Array" | "ReadonlyArray", [elem]) => {
+            let (_, elem) = ts_type_to_rs(st, ctxt, elem, comment, lkm);
+            return RustType::Array(Box::new(elem));
+        }
+        ("Partial", [_]) => Derivation::Partial,
+        ("Pick", [_, keys]) => Derivation::Pick(literal_keys(keys)),
+        ("Omit", [_, keys]) => Derivation::Omit(literal_keys(keys)),
+        _ => return RustType::Custom(TypeName::new(name.to_owned())),
+    };
+    let ctxt = ctxt.as_mut().expect("provide ctxt");
+    let derived = st.create_ident(ctxt);
+    let (_, base) = ts_type_to_rs(st, &mut Some(ctxt.clone()), params[0], None, lkm);
+    utility_types::derive(st, derived, base, derivation, comment)
+}
+
+/// `"a" | "b"` of `Pick<T, "a" | "b">`
+fn literal_keys(keys: &swc_ecma_ast::TsType) -> Vec<String> {
+    let lit = |t: &swc_ecma_ast::TsType| Some(t.as_ts_lit_type()?.lit.as_str()?.value.to_string());
+    match keys {
+        swc_ecma_ast::TsType::TsUnionOrIntersectionType(
+            swc_ecma_ast::TsUnionOrIntersectionType::TsUnionType(u),
+        ) => u.types.iter().flat_map(|t| lit(t)).collect(),
+        t => lit(t).into_iter().collect(),
+    }
+}
+
+/// `export type Name = Ref<..>;`
+pub fn talias_type_ref<'input>(
+    st: &mut FrontendState<'input, '_>,
+    name: &'input str,
+    tref: &'input swc_ecma_ast::TsTypeRef,
+    comment: Option<RustComment>,
+    lkm: &mut LiteralKeyMap,
+) {
+    let mut ctxt = Some(TypeConvertContext {
+        path: vec![Cow::Borrowed(name)],
+        granted_name: Some(name),
+        from_alias: true,
+        ..Default::default()
+    });
+    let ty = type_ref(st, &mut ctxt, tref, comment.clone(), lkm);
+    if ty.as_custom().map(|t| t.name.as_str()) == Some(name) {
+        // derived struct took the name
+        return;
+    }
+    st.segments.push(RustSegment::Alias(RustAlias {
+        name: name.to_owned(),
+        is_borrowed: false,
+        comment,
+        ty,
+    }));
 }
 
 pub struct FrontendState<'input, 'output> {
     pub segments: &'output mut Vec<RustSegment>,
     pub comments: &'input swc_common::comments::SingleThreadedComments,
     pub name_types: name_types::State<'input>,
+    pub utility_types: utility_types::State,
+    /// generated type name -> path it came from
+    pub origins: Origins,
 }
 
 impl<'input, 'output> FrontendState<'input, 'output> {
@@ -357,6 +626,32 @@ impl<'input, 'output> FrontendState<'input, 'output> {
         self.segments.push(value);
         RustType::Custom(TypeName::new(name))
     }
+    /// create identifier from path of `ctxt` and remember the path
+    pub fn create_ident(&mut self, ctxt: &mut TypeConvertContext) -> String {
+        self.create_ident_with(ctxt, None)
+    }
+
+    pub fn create_ident_with(
+        &mut self,
+        ctxt: &mut TypeConvertContext,
+        additional: Option<Vec<String>>,
+    ) -> String {
+        let (name, origin) = ctxt.ident_and_origin(additional);
+        if let Some(origin) = origin {
+            self.origins.insert(name.to_owned(), origin);
+        }
+        name
+    }
+
+    /// append `suffix` to the name created by [`Self::create_ident`]
+    pub fn suffix_ident(&mut self, name: &mut String, suffix: &str) {
+        let origin = self.origins.remove(name.as_str());
+        name.push_str(suffix);
+        if let Some(origin) = origin {
+            self.origins.insert(name.to_owned(), origin);
+        }
+    }
+
     pub fn get_comment(&self, pos: swc_common::BytePos) -> Option<RustComment> {
         self.comments
             .with_leading(pos, |cs| cs.last().map(|c| strip_docs(&c.text)))
@@ -417,26 +712,27 @@ impl<'a> TypeConvertContext<'a> {
             .collect()
     }
 
-    /// create identifier from path
-    pub fn create_ident(&mut self) -> String {
-        self.create_ident_with(None)
-    }
-
-    pub fn create_ident_with(&mut self, additional: Option<Vec<String>>) -> String {
+    /// create identifier from path, with the dotted path (e.g. `IssuesEvent.issue.user`)
+    /// it came from unless the name is granted
+    fn ident_and_origin(&mut self, additional: Option<Vec<String>>) -> (String, Option<String>) {
         if let Some(name) = self.granted_name.take() {
-            return name.to_owned();
+            return (name.to_owned(), None);
         }
         let mut v = self.to_pascal();
+        let mut origin = self.path.join(".");
         if let Some(additional) = additional {
+            origin.push('#');
+            origin.push_str(&additional.concat());
             v.extend(additional);
         } else {
             if self.from_alias || self.duplicate_counter != 0 {
                 let suffix = self.duplicate_counter + self.from_alias as usize;
+                origin.push_str(&format!("#{suffix}"));
                 v.push(suffix.to_string());
             }
             self.duplicate_counter += 1;
         }
-        v.concat()
+        (v.concat(), Some(origin))
     }
 }
 
@@ -456,17 +752,23 @@ pub fn ts_type_to_rs<'input>(
 
     let typ = match typ {
         swc_ecma_ast::TsType::TsKeywordType(tk) => ts_keyword_type_to_rs(tk),
+        swc_ecma_ast::TsType::TsUnionOrIntersectionType(
+            swc_ecma_ast::TsUnionOrIntersectionType::TsIntersectionType(tints),
+        ) => {
+            // share ctxt so that sibling intersections (e.g. variants of union) get distinct names
+            intersection(
+                st,
+                ctxt.as_mut().expect("provide ctxt"),
+                tints,
+                comment,
+                lkm,
+            )
+        }
         swc_ecma_ast::TsType::TsUnionOrIntersectionType(tsuoi) => {
             union_or_intersection(st, ctxt.to_owned(), tsuoi, comment, &mut nullable, lkm)
         }
         swc_ecma_ast::TsType::TsLitType(_tslit) => RustType::UnknownLiteral,
-        swc_ecma_ast::TsType::TsTypeRef(tref) => {
-            let id = tref.type_name.as_ident().unwrap().sym.as_ref();
-            RustType::Custom(TypeName {
-                name: id.to_owned(),
-                is_borrowed: false,
-            })
-        }
+        swc_ecma_ast::TsType::TsTypeRef(tref) => type_ref(st, ctxt, tref, comment, lkm),
         swc_ecma_ast::TsType::TsArrayType(tarray) => {
             let (_n, etype) = ts_type_to_rs(st, ctxt, &tarray.elem_type, comment, lkm);
             //format!("Vec<{etype}>")
@@ -490,7 +792,7 @@ pub fn ts_type_to_rs<'input>(
             if t.elem_types.is_empty() {
                 RustType::Unit
             } else {
-                RustType::Unknown
+                tuple_type(st, ctxt, t, lkm)
             }
         }
         _ => {
@@ -0,0 +1,516 @@
+//! frontend for JSON Schema (`schema.json` of octokit/webhooks)
+//!
+//! builds the same [`RustSegment`] list as the TypeScript frontend,
+//! so that transformers and the backend are shared between them.
+
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+};
+
+use serde_json::{Map, Value};
+
+use crate::ir::{
+    LiteralKeyMap, LiteralValue, RustAlias, RustComment, RustContainerAttrs, RustEnum,
+    RustEnumMember, RustEnumMemberKind, RustFieldAttr, RustFieldAttrs, RustMemberType, RustSegment,
+    RustStruct, RustStructAttr, RustStructMember, RustType, RustVariantAttrs, SerdeContainerAttr,
+    SerdeFieldAttr, TypeName,
+};
+
+use super::{name_types, rename_field, FrontendState, TypeConvertContext};
+
+pub struct JsonSchemaState<'input> {
+    /// prefix of `$ref` pointing definitions (e.g. `#/definitions/`)
+    ref_prefix: &'input str,
+    /// definition key -> rust type name
+    names: &'input HashMap<String, String>,
+    /// definitions marked `"nullable": true` (OpenAPI 3.0)
+    nullable: HashSet<&'input str>,
+}
+
+impl<'input> JsonSchemaState<'input> {
+    pub fn new(
+        ref_prefix: &'input str,
+        definitions: &'input Map<String, Value>,
+        names: &'input HashMap<String, String>,
+    ) -> Self {
+        let nullable = definitions
+            .iter()
+            .filter(|(_, d)| d.get("nullable").and_then(Value::as_bool) == Some(true))
+            .map(|(k, _)| k.as_str())
+            .collect();
+        Self {
+            ref_prefix,
+            names,
+            nullable,
+        }
+    }
+
+    fn definition_key<'r>(&self, r: &'r str) -> &'r str {
+        r.strip_prefix(self.ref_prefix)
+            .unwrap_or_else(|| panic!("unsupported $ref: {r}"))
+    }
+
+    fn resolve_ref(&self, r: &str) -> String {
+        let key = self.definition_key(r);
+        match self.names.get(key) {
+            Some(name) => name.to_owned(),
+            None => panic!("unknown definition: {key}"),
+        }
+    }
+
+    fn is_nullable_ref(&self, r: &str) -> bool {
+        self.nullable.contains(self.definition_key(r))
+    }
+}
+
+/// name of the type generated from a definition, as json-schema-to-typescript does
+pub fn definition_name(key: &str, schema: &Value) -> String {
+    let title = schema.get("title").and_then(Value::as_str).unwrap_or(key);
+    title
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .map(|term| {
+            let mut term = term.to_owned();
+            if let Some(c) = term.chars().next() {
+                term.replace_range(..1, &c.to_ascii_uppercase().to_string());
+            }
+            term
+        })
+        .collect::<Vec<_>>()
+        .concat()
+}
+
+/// names of all definitions. falls back to the name from key if titles conflict,
+/// then to numbered names (`Name2`, `Name3`, ...)
+pub fn definition_names(definitions: &Map<String, Value>) -> HashMap<String, String> {
+    let mut names = HashMap::new();
+    let mut used = HashSet::new();
+    for (key, schema) in definitions {
+        let mut name = definition_name(key, schema);
+        if used.contains(&name) {
+            name = definition_name(key, &Value::Null);
+        }
+        let base = name.clone();
+        let mut n = 2;
+        while used.contains(&name) {
+            name = format!("{base}{n}");
+            n += 1;
+        }
+        used.insert(name.clone());
+        names.insert(key.to_owned(), name);
+    }
+    names
+}
+
+/// convert one named schema (a definition or the root) into segments
+pub fn definition2segment<'input>(
+    st: &mut FrontendState<'input, '_>,
+    jst: &JsonSchemaState<'input>,
+    name: &'input str,
+    schema: &'input Value,
+    lkm: &mut LiteralKeyMap,
+) {
+    let comment = get_comment(schema);
+    let mut ctxt = TypeConvertContext {
+        path: vec![Cow::Borrowed(name)],
+        granted_name: Some(name),
+        from_alias: true,
+        ..Default::default()
+    };
+    if is_object(schema) {
+        let s = object2struct(st, jst, schema, comment, &mut ctxt, lkm);
+        st.segments.push(RustSegment::Struct(s));
+        return;
+    }
+    if let Some(variants) = string_enum(schema) {
+        if variants.len() > 1 {
+            name_types::string_literal_union(st, sorted(variants), comment, &mut ctxt);
+            return;
+        }
+    }
+    let (_, ty) = schema_to_rs(st, jst, &mut Some(ctxt), schema, None, lkm);
+    if ty.as_custom().map(|t| t.name.as_str()) == Some(name) {
+        // segment is already pushed with granted name
+        return;
+    }
+    st.segments.push(RustSegment::Alias(RustAlias {
+        name: name.to_owned(),
+        is_borrowed: false,
+        comment,
+        ty,
+    }));
+}
+
+fn is_object(schema: &Value) -> bool {
+    schema.get("properties").is_some()
+}
+
+fn object2struct<'input>(
+    st: &mut FrontendState<'input, '_>,
+    jst: &JsonSchemaState<'input>,
+    schema: &'input Value,
+    comment: Option<RustComment>,
+    ctxt: &mut TypeConvertContext<'input>,
+    lkm: &mut LiteralKeyMap,
+) -> RustStruct {
+    let name = st.create_ident(ctxt);
+    let member = object_members(st, jst, schema, ctxt, &name, lkm);
+    RustStruct::from_members(name, comment, member.into_iter())
+}
+
+fn object_members<'input>(
+    st: &mut FrontendState<'input, '_>,
+    jst: &JsonSchemaState<'input>,
+    schema: &'input Value,
+    ctxt: &mut TypeConvertContext<'input>,
+    name: &str,
+    lkm: &mut LiteralKeyMap,
+) -> Vec<RustStructMember> {
+    let required: Vec<&str> = schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|r| r.iter().flat_map(Value::as_str).collect())
+        .unwrap_or_default();
+    let mut member = Vec::new();
+    if let Some(props) = schema.get("properties").and_then(Value::as_object) {
+        for (pkey, pschema) in props {
+            let comment = get_comment(pschema);
+            let (pname, attr) = rename_field(pkey);
+            let mut ctxt = ctxt.clone();
+            ctxt.projection(Cow::Owned(crate::ident::unraw(&pname).to_owned()));
+            let (nullable, mut ty) = schema_to_rs(st, jst, &mut Some(ctxt), pschema, None, lkm);
+            if let Some([lit]) = string_enum(pschema).as_deref() {
+                ty = RustType::Literal(LiteralValue::String(lit.to_string()));
+                lkm.entry(name.to_owned())
+                    .or_default()
+                    .insert(pkey.to_owned(), lit.to_string());
+            }
+            member.push(RustStructMember {
+                attr,
+                name: pname,
+                ty: RustMemberType {
+                    ty,
+                    is_optional: !required.contains(&pkey.as_str()),
+                    is_nullable: nullable,
+                },
+                comment,
+            });
+        }
+    }
+    if let Some(additional) = schema.get("additionalProperties").filter(|a| a.is_object()) {
+        let mut ctxt = Some(ctxt.clone());
+        let (_, value_ty) = schema_to_rs(st, jst, &mut ctxt, additional, None, lkm);
+        member.push(RustStructMember {
+            ty: RustMemberType {
+                ty: RustType::Map(
+                    Box::new(RustType::String { is_borrowed: false }),
+                    Box::new(value_ty),
+                ),
+                is_optional: false,
+                is_nullable: false,
+            },
+            name: "additional_properties".to_owned(),
+            attr: RustFieldAttrs::from_attr(RustFieldAttr::Serde(SerdeFieldAttr::Flatten)),
+            comment: None,
+        });
+    }
+    member
+}
+
+/// values of `enum` or `const` if all of them are string (`null` is ignored)
+fn string_enum(schema: &Value) -> Option<Vec<&str>> {
+    if let Some(c) = schema.get("const") {
+        return Some(vec![c.as_str()?]);
+    }
+    schema
+        .get("enum")?
+        .as_array()?
+        .iter()
+        .filter(|v| !v.is_null())
+        .map(Value::as_str)
+        .collect()
+}
+
+fn sorted(mut variants: Vec<&str>) -> Vec<&str> {
+    variants.sort();
+    variants
+}
+
+fn is_null_schema(schema: &Value) -> bool {
+    schema.get("type").and_then(Value::as_str) == Some("null")
+}
+
+pub fn schema_to_rs<'input>(
+    st: &mut FrontendState<'input, '_>,
+    jst: &JsonSchemaState<'input>,
+    ctxt: &mut Option<TypeConvertContext<'input>>,
+    schema: &'input Value,
+    comment: Option<RustComment>,
+    lkm: &mut LiteralKeyMap,
+) -> (bool, RustType) {
+    let mut nullable = false;
+
+    if !schema.is_object() {
+        // `true` schema accepts anything
+        return (false, RustType::Unknown);
+    }
+
+    if let Some(r) = schema.get("$ref").and_then(Value::as_str) {
+        let ty = RustType::Custom(TypeName::new(jst.resolve_ref(r)));
+        return (jst.is_nullable_ref(r), ty);
+    }
+
+    if let Some(e) = schema.get("enum").and_then(Value::as_array) {
+        nullable = e.iter().any(Value::is_null);
+    }
+    // OpenAPI 3.0 extension
+    if schema.get("nullable").and_then(Value::as_bool) == Some(true) {
+        nullable = true;
+    }
+    if let Some(variants) = string_enum(schema) {
+        if variants.len() == 1 {
+            return (nullable, RustType::UnknownLiteral);
+        }
+        let ct = ctxt.as_mut().expect("provide ctxt");
+        let tn = name_types::string_literal_union(st, sorted(variants), comment, ct);
+        return (nullable, RustType::Custom(tn));
+    }
+
+    if let Some(variants) = schema
+        .get("oneOf")
+        .or_else(|| schema.get("anyOf"))
+        .and_then(Value::as_array)
+    {
+        let ty = union(
+            st,
+            jst,
+            ctxt.to_owned(),
+            variants,
+            comment,
+            &mut nullable,
+            lkm,
+        );
+        return (nullable, ty);
+    }
+
+    if let Some(parts) = schema.get("allOf").and_then(Value::as_array) {
+        if let [part] = parts.as_slice() {
+            let (n, t) = schema_to_rs(st, jst, ctxt, part, comment, lkm);
+            return (nullable || n, t);
+        }
+        let ty = intersection(st, jst, ctxt.to_owned(), parts, comment, lkm);
+        return (nullable, ty);
+    }
+
+    let typ = match schema.get("type") {
+        Some(Value::String(t)) => t.as_str(),
+        Some(Value::Array(ts)) => {
+            let mut ts: Vec<&str> = ts.iter().flat_map(Value::as_str).collect();
+            ts.retain(|t| {
+                if *t == "null" {
+                    nullable = true;
+                    return false;
+                }
+                true
+            });
+            match ts[..] {
+                [t] => t,
+                _ => return (nullable, RustType::Unknown),
+            }
+        }
+        _ if is_object(schema) => "object",
+        _ => return (nullable, RustType::Unknown),
+    };
+
+    let ty = match typ {
+        "string" => match schema.get("format").and_then(Value::as_str) {
+            Some(format) => string_format(st, jst, format),
+            None => RustType::String { is_borrowed: false },
+        },
+        "integer" => RustType::Number,
+        "number" => RustType::Float,
+        "boolean" => RustType::Boolean,
+        "null" => RustType::Unit,
+        "array" => match schema.get("items") {
+            Some(items) => {
+                let (_, etype) = schema_to_rs(st, jst, ctxt, items, comment, lkm);
+                RustType::Array(Box::new(etype))
+            }
+            None => RustType::Array(Box::new(RustType::Unknown)),
+        },
+        "object" if is_object(schema) => {
+            let s = object2struct(st, jst, schema, comment, ctxt.as_mut().unwrap(), lkm);
+            st.push_segment(RustSegment::Struct(s))
+        }
+        "object" => {
+            // `{ [k: string]: T }`
+            let value_ty = match schema.get("additionalProperties") {
+                Some(additional) if additional.is_object() => {
+                    schema_to_rs(st, jst, ctxt, additional, None, lkm).1
+                }
+                _ => RustType::Unknown,
+            };
+            RustType::Map(
+                Box::new(RustType::String { is_borrowed: false }),
+                Box::new(value_ty),
+            )
+        }
+        _ => RustType::Unknown,
+    };
+
+    (nullable, ty)
+}
+
+/// string formats typed as aliases of `String`: `format` -> (alias, comment)
+const STRING_FORMATS: &[(&str, &str, &str)] = &[
+    (
+        "date-time",
+        "DateTime",
+        "RFC 3339 date-time, e.g. `2024-01-01T12:00:00Z`",
+    ),
+    ("date", "Date", "RFC 3339 full-date, e.g. `2024-01-01`"),
+    ("uri", "Uri", "URI"),
+    ("uri-template", "UriTemplate", "RFC 6570 URI template"),
+    ("email", "Email", "email address"),
+];
+
+/// alias of `String` telling `format` (e.g. `DateTime`), pushed on first use.
+/// unknown formats, and ones whose alias name is taken by a definition, are plain strings.
+fn string_format(
+    st: &mut FrontendState<'_, '_>,
+    jst: &JsonSchemaState<'_>,
+    format: &str,
+) -> RustType {
+    let string = RustType::String { is_borrowed: false };
+    let Some((_, name, comment)) = STRING_FORMATS.iter().find(|(f, ..)| *f == format) else {
+        return string;
+    };
+    if jst.names.values().any(|n| n == name) {
+        return string;
+    }
+    let ty = RustType::Custom(TypeName::new(name.to_string()));
+    if st.segments.iter().any(|s| s.name() == *name) {
+        return ty;
+    }
+    st.push_segment(RustSegment::Alias(RustAlias {
+        name: name.to_string(),
+        is_borrowed: false,
+        comment: Some(RustComment(comment.to_string())),
+        ty: string,
+    }))
+}
+
+fn union<'input>(
+    st: &mut FrontendState<'input, '_>,
+    jst: &JsonSchemaState<'input>,
+    mut ctxt: Option<TypeConvertContext<'input>>,
+    variants: &'input [Value],
+    comment: Option<RustComment>,
+    nullable: &mut bool,
+    lkm: &mut LiteralKeyMap,
+) -> RustType {
+    // obtain non-null types within union, judging if there is null type
+    let variants: Vec<&Value> = variants
+        .iter()
+        .filter(|v| {
+            if is_null_schema(v) {
+                *nullable = true;
+                return false;
+            }
+            true
+        })
+        .collect();
+
+    assert!(!variants.is_empty());
+    if variants.len() == 1 {
+        let (n, t) = schema_to_rs(st, jst, &mut ctxt, variants[0], comment, lkm);
+        *nullable |= n;
+        return t;
+    }
+
+    // strings check: { "enum": ["Bot"] } | { "enum": ["User", "Organization"] }
+    if let Some(strs) = variants
+        .iter()
+        .map(|v| string_enum(v))
+        .collect::<Option<Vec<_>>>()
+    {
+        let ct = ctxt.as_mut().expect("provide ctxt");
+        let tn = name_types::string_literal_union(st, sorted(strs.concat()), comment, ct);
+        return RustType::Custom(tn);
+    }
+
+    let type_convert_context = ctxt.as_mut().unwrap();
+    let mut name = st.create_ident(type_convert_context);
+    if !type_convert_context.from_alias {
+        st.suffix_ident(&mut name, "Union");
+    }
+    let member = variants
+        .iter()
+        .map(|v| {
+            let (_, t) = schema_to_rs(st, jst, &mut ctxt, v, None, lkm);
+            RustEnumMember {
+                attr: RustVariantAttrs::new(),
+                kind: RustEnumMemberKind::Unary(t),
+            }
+        })
+        .collect();
+
+    st.push_segment(RustSegment::Enum(RustEnum {
+        attr: RustContainerAttrs::from_attr(RustStructAttr::Serde(SerdeContainerAttr::Untagged)),
+        name,
+        comment,
+        is_borrowed: false,
+        member,
+    }))
+}
+
+fn intersection<'input>(
+    st: &mut FrontendState<'input, '_>,
+    jst: &JsonSchemaState<'input>,
+    ctxt: Option<TypeConvertContext<'input>>,
+    parts: &'input [Value],
+    comment: Option<RustComment>,
+    lkm: &mut LiteralKeyMap,
+) -> RustType {
+    let mut ctxt = ctxt.expect("provide ctxt");
+    let name = st.create_ident(&mut ctxt);
+    let mut member = Vec::new();
+    for part in parts {
+        if let Some(r) = part.get("$ref").and_then(Value::as_str) {
+            // flatten referenced type into the struct
+            let tname = jst.resolve_ref(r);
+            let mut field_name = tname.to_owned();
+            crate::case::CaseConvention::Pascal
+                .into_rename_rule()
+                .convert_to_snake(&mut field_name);
+            member.push(RustStructMember {
+                attr: RustFieldAttrs::from_attr(RustFieldAttr::Serde(SerdeFieldAttr::Flatten)),
+                name: field_name,
+                ty: RustMemberType {
+                    is_optional: false,
+                    is_nullable: false,
+                    ty: RustType::Custom(TypeName::new(tname)),
+                },
+                comment: None,
+            });
+        } else {
+            member.extend(object_members(st, jst, part, &mut ctxt, &name, lkm));
+        }
+    }
+    st.push_segment(RustSegment::Struct(RustStruct::from_members(
+        name,
+        comment,
+        member.into_iter(),
+    )))
+}
+
+fn get_comment(schema: &Value) -> Option<RustComment> {
+    let description = schema.get("description")?.as_str()?;
+    Some(RustComment(
+        description
+            .lines()
+            .map(str::trim)
+            .collect::<Vec<_>>()
+            .join(" "),
+    ))
+}
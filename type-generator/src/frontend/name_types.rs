@@ -1,8 +1,11 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
-use crate::ir::{
-    RustAlias, RustComment, RustEnum, RustEnumMember, RustEnumMemberKind, RustSegment, RustStruct,
-    RustType, RustVariantAttr, RustVariantAttrs, SerdeVariantAttr, TypeName,
+use crate::{
+    ident,
+    ir::{
+        RustAlias, RustComment, RustEnum, RustEnumMember, RustEnumMemberKind, RustSegment,
+        RustStruct, RustType, RustVariantAttr, RustVariantAttrs, SerdeVariantAttr, TypeName,
+    },
 };
 
 use super::{ts_index_signature, ts_prop_signature, FrontendState, TypeConvertContext};
@@ -19,7 +22,7 @@ pub fn string_literal_union<'input>(
     comment: Option<RustComment>,
     path: &mut TypeConvertContext,
 ) -> TypeName {
-    let name = path.create_ident();
+    let name = st.create_ident(path);
 
     TypeName::new(match st.name_types.literal_map.get(&variants) {
         Some(s) => {
@@ -37,6 +40,34 @@ pub fn string_literal_union<'input>(
     })
 }
 
+/// `0 | 1 | -1` becomes enum with variants `N0`, `N1` and `NMinus1`
+pub fn numeric_literal_union(
+    st: &mut FrontendState,
+    mut variants: Vec<i64>,
+    comment: Option<RustComment>,
+    path: &mut TypeConvertContext,
+) -> TypeName {
+    let name = st.create_ident(path);
+    variants.sort();
+    variants.dedup();
+    st.segments.push(RustSegment::Enum(RustEnum::from_members(
+        name.to_owned(),
+        comment,
+        variants.into_iter().map(|value| RustEnumMember {
+            attr: RustVariantAttrs::new(),
+            kind: RustEnumMemberKind::Discriminant {
+                variant_name: if value < 0 {
+                    format!("NMinus{}", value.unsigned_abs())
+                } else {
+                    format!("N{value}")
+                },
+                value,
+            },
+        }),
+    )));
+    TypeName::new(name)
+}
+
 fn create_alias(
     st: &mut FrontendState,
     comment: Option<RustComment>,
@@ -52,11 +83,21 @@ fn create_alias(
 }
 
 fn create_enum(st: &mut FrontendState, comment: Option<RustComment>, name: &String, vs: &[&str]) {
+    let mut seen = HashSet::new();
     st.segments.push(RustSegment::Enum(RustEnum::from_members(
         name.to_owned(),
         comment,
         vs.iter().map(|&v| {
-            let renamed = rename_to_valid_ident(v);
+            let mut renamed = ident::type_name(rename_to_valid_ident(v));
+            // e.g. "foo-bar" and "foo_bar"
+            if !seen.insert(renamed.clone()) {
+                let base = renamed;
+                let mut n = 2;
+                while !seen.insert(format!("{base}{n}")) {
+                    n += 1;
+                }
+                renamed = format!("{base}{n}");
+            }
             let mut attr = RustVariantAttrs::new();
             if v != renamed {
                 attr.add_attr(RustVariantAttr::Serde(SerdeVariantAttr::Rename(
@@ -108,7 +149,7 @@ pub fn type_literal<'input>(
     ctxt: &mut TypeConvertContext<'input>,
     lkm: &mut HashMap<String, HashMap<String, String>>,
 ) -> RustStruct {
-    let name = ctxt.create_ident();
+    let name = st.create_ident(ctxt);
     RustStruct::from_members(
         name.to_owned(),
         comment,
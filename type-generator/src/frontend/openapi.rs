@@ -0,0 +1,77 @@
+//! frontend for OpenAPI 3 description (e.g. `api.github.com.json` of github/rest-api-description)
+//!
+//! component schemas are JSON Schema, so conversion is delegated to [`super::json_schema`].
+
+use std::collections::{HashMap, HashSet};
+
+use serde_json::{Map, Value};
+
+pub const REF_PREFIX: &str = "#/components/schemas/";
+
+pub fn component_schemas(openapi: &Value) -> &Map<String, Value> {
+    openapi
+        .get("components")
+        .and_then(|c| c.get("schemas"))
+        .and_then(Value::as_object)
+        .expect("OpenAPI description has no components.schemas")
+}
+
+/// component schemas are named by their keys, as titles often conflict
+/// (e.g. `simple-user` and `nullable-simple-user` are both titled "Simple User")
+pub fn component_names(schemas: &Map<String, Value>) -> HashMap<String, String> {
+    schemas
+        .keys()
+        .map(|key| {
+            (
+                key.to_owned(),
+                super::json_schema::definition_name(key, &Value::Null),
+            )
+        })
+        .collect()
+}
+
+/// keys of the selected component schemas and all schemas they refer to, in declaration order
+pub fn select_schemas<'input>(
+    schemas: &'input Map<String, Value>,
+    selected: &[&str],
+) -> Vec<&'input str> {
+    let mut reachable = HashSet::new();
+    let mut stack: Vec<&str> = selected.to_vec();
+    while let Some(key) = stack.pop() {
+        let (key, schema) = schemas
+            .get_key_value(key)
+            .unwrap_or_else(|| panic!("unknown component schema: {key}"));
+        if reachable.insert(key.as_str()) {
+            collect_refs(schema, &mut stack);
+        }
+    }
+    schemas
+        .keys()
+        .map(String::as_str)
+        .filter(|k| reachable.contains(k))
+        .collect()
+}
+
+fn collect_refs<'input>(schema: &'input Value, refs: &mut Vec<&'input str>) {
+    match schema {
+        Value::Object(o) => {
+            for (k, v) in o {
+                match (k.as_str(), v) {
+                    ("$ref", Value::String(r)) => {
+                        let key = r
+                            .strip_prefix(REF_PREFIX)
+                            .unwrap_or_else(|| panic!("unsupported $ref: {r}"));
+                        refs.push(key);
+                    }
+                    _ => collect_refs(v, refs),
+                }
+            }
+        }
+        Value::Array(a) => {
+            for v in a {
+                collect_refs(v, refs);
+            }
+        }
+        _ => (),
+    }
+}
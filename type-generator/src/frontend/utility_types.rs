@@ -0,0 +1,144 @@
+//! TypeScript utility types deriving a struct from another one (`Partial`, `Pick` and `Omit`)
+//!
+//! the base type may be declared after the reference, so derived structs are pushed as
+//! placeholders and filled by [`resolve`] after the whole module is processed.
+
+use std::collections::HashSet;
+
+use crate::{
+    ir::{
+        Origins, RustAlias, RustComment, RustFieldAttr, RustSegment, RustStruct, RustType,
+        SerdeFieldAttr, TypeName,
+    },
+    visit::{self, Visit},
+};
+
+use super::FrontendState;
+
+pub enum Derivation {
+    /// `Partial<T>`
+    Partial,
+    /// `Pick<T, "a" | "b">`
+    Pick(Vec<String>),
+    /// `Omit<T, "a" | "b">`
+    Omit(Vec<String>),
+}
+
+struct Pending {
+    name: String,
+    base: RustType,
+    derivation: Derivation,
+}
+
+#[derive(Default)]
+pub struct State {
+    pending: Vec<Pending>,
+}
+
+/// push a placeholder struct named `name` derived from `base`
+pub fn derive(
+    st: &mut FrontendState,
+    name: String,
+    base: RustType,
+    derivation: Derivation,
+    comment: Option<RustComment>,
+) -> RustType {
+    st.utility_types.pending.push(Pending {
+        name: name.to_owned(),
+        base,
+        derivation,
+    });
+    st.push_segment(RustSegment::Struct(RustStruct::from_members(
+        name,
+        comment,
+        std::iter::empty(),
+    )))
+}
+
+/// fill placeholders in order of registration, so that nested derivations
+/// (e.g. `Partial<Pick<T, "a">>`) see their resolved base.
+///
+/// if the base is not a struct, the placeholder becomes an alias of it.
+/// intermediate structs (e.g. `Pick<T, "a">` of `Partial<Pick<T, "a">>`) are removed
+/// unless referred to.
+pub fn resolve(segments: &mut Vec<RustSegment>, origins: &mut Origins, state: State) {
+    let derived: HashSet<String> = state.pending.iter().map(|p| p.name.to_owned()).collect();
+    let intermediates: HashSet<String> = state
+        .pending
+        .iter()
+        .filter_map(|p| Some(p.base.as_custom()?.name.to_owned()))
+        .filter(|base| derived.contains(base))
+        .collect();
+    for Pending {
+        name,
+        base,
+        derivation,
+    } in state.pending
+    {
+        let base_members = base_struct(segments, &base).map(|s| s.member.clone());
+        let placeholder = segments
+            .iter_mut()
+            .find(|s| s.name() == name)
+            .expect("placeholder of derived struct");
+        let Some(mut member) = base_members else {
+            let comment = match placeholder {
+                RustSegment::Struct(s) => s.comment.take(),
+                _ => None,
+            };
+            *placeholder = RustSegment::Alias(RustAlias {
+                name,
+                is_borrowed: false,
+                comment,
+                ty: base,
+            });
+            continue;
+        };
+        match derivation {
+            Derivation::Partial => {
+                for m in &mut member {
+                    if !m
+                        .attr
+                        .as_inner()
+                        .contains(&RustFieldAttr::Serde(SerdeFieldAttr::Flatten))
+                    {
+                        m.ty.is_optional = true;
+                    }
+                }
+            }
+            Derivation::Pick(keys) => member.retain(|m| keys.iter().any(|k| k == m.serde_name())),
+            Derivation::Omit(keys) => member.retain(|m| keys.iter().all(|k| k != m.serde_name())),
+        }
+        if let RustSegment::Struct(s) = placeholder {
+            s.member = member;
+        }
+    }
+
+    struct Referred(HashSet<String>);
+    impl Visit for Referred {
+        fn visit_type_name(&mut self, type_name: &TypeName) {
+            self.0.insert(type_name.name.to_owned());
+        }
+    }
+    let mut referred = Referred(HashSet::new());
+    visit::walk_segments(&mut referred, segments);
+    let unused: HashSet<String> = intermediates.difference(&referred.0).cloned().collect();
+    segments.retain(|s| !unused.contains(s.name()));
+    origins.retain(|name, _| !unused.contains(name));
+}
+
+/// follow aliases to the struct
+fn base_struct<'a>(segments: &'a [RustSegment], base: &RustType) -> Option<&'a RustStruct> {
+    let mut ty = base;
+    // bounded to avoid looping on cyclic aliases
+    for _ in 0..segments.len() {
+        let RustType::Custom(TypeName { name, .. }) = ty else {
+            return None;
+        };
+        match segments.iter().find(|s| s.name() == name)? {
+            RustSegment::Struct(s) => return Some(s),
+            RustSegment::Alias(a) => ty = &a.ty,
+            _ => return None,
+        }
+    }
+    None
+}
@@ -0,0 +1,153 @@
+//! sanitize property keys and literals into Rust identifiers
+
+use std::collections::HashSet;
+
+use crate::ir::{RustFieldAttr, RustSegment, RustStructMember, SerdeFieldAttr};
+
+/// strict and reserved keywords of Rust 2021
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+    "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true", "type",
+    "unsafe", "use", "where", "while", "abstract", "become", "box", "do", "final", "macro",
+    "override", "priv", "try", "typeof", "unsized", "virtual", "yield",
+];
+
+/// keywords which cannot be raw identifiers
+const NON_RAW_KEYWORDS: &[&str] = &["crate", "self", "Self", "super"];
+
+/// renames kept for compatibility with earlier generated code
+const LEGACY_FIELD_NAMES: &[(&str, &str)] = &[
+    ("type", "type_"),
+    ("ref", "ref_"),
+    ("self", "self_"),
+    ("+1", "plus_1"),
+    ("-1", "minus_1"),
+];
+
+pub fn is_keyword(s: &str) -> bool {
+    KEYWORDS.contains(&s)
+}
+
+/// `r#type` -> `type`
+pub fn unraw(s: &str) -> &str {
+    s.strip_prefix("r#").unwrap_or(s)
+}
+
+/// replace characters not allowed in identifiers with `_`
+fn escape(s: &str) -> String {
+    let mut escaped: String = s
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if escaped.starts_with(|c: char| c.is_ascii_digit()) {
+        escaped.insert(0, '_');
+    }
+    if escaped.chars().all(|c| c == '_') {
+        escaped.push_str("field");
+    }
+    escaped
+}
+
+/// identifier of a field for the property key.
+///
+/// keywords become raw identifiers (or get `_` suffixed if not allowed to be raw),
+/// and other invalid keys are escaped.
+pub fn field_name(key: &str) -> String {
+    if let Some((_, renamed)) = LEGACY_FIELD_NAMES.iter().find(|(k, _)| *k == key) {
+        return (*renamed).to_owned();
+    }
+    let name = escape(key);
+    if NON_RAW_KEYWORDS.contains(&name.as_str()) {
+        format!("{name}_")
+    } else if is_keyword(&name) {
+        format!("r#{name}")
+    } else {
+        name
+    }
+}
+
+/// make an identifier in Pascal case (e.g. name of variant) valid
+pub fn type_name(name: String) -> String {
+    if name.is_empty() {
+        "Empty".to_owned()
+    } else if is_keyword(&name) {
+        format!("{name}_")
+    } else {
+        name
+    }
+}
+
+/// suffix `_2`, `_3`, ... to fields with the same name in a struct.
+/// fields named as their key keep the name, and others are suffixed in order.
+/// renamed fields keep their key by `#[serde(rename)]`.
+pub fn dedup_fields(segments: &mut [RustSegment]) {
+    for segment in segments {
+        let RustSegment::Struct(s) = segment else {
+            continue;
+        };
+        let is_verbatim = |m: &RustStructMember| m.serde_name() == unraw(&m.name);
+        let mut seen: HashSet<_> = s
+            .member
+            .iter()
+            .filter(|m| is_verbatim(m))
+            .map(|m| m.name.to_owned())
+            .collect();
+        for m in &mut s.member {
+            if is_verbatim(m) || seen.insert(m.name.to_owned()) {
+                continue;
+            }
+            let key = m.serde_name().to_owned();
+            let base = unraw(&m.name).to_owned();
+            let mut n = 2;
+            while seen.contains(&format!("{base}_{n}")) {
+                n += 1;
+            }
+            m.name = format!("{base}_{n}");
+            seen.insert(m.name.to_owned());
+            if !m
+                .attr
+                .as_inner()
+                .iter()
+                .any(|a| matches!(a, RustFieldAttr::Serde(SerdeFieldAttr::Rename(_))))
+            {
+                m.attr
+                    .add_attr(RustFieldAttr::Serde(SerdeFieldAttr::Rename(key)));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_field_name() {
+        assert_eq!(field_name("login"), "login");
+        assert_eq!(field_name("type"), "type_");
+        assert_eq!(field_name("+1"), "plus_1");
+        assert_eq!(field_name("async"), "r#async");
+        assert_eq!(field_name("match"), "r#match");
+        assert_eq!(field_name("super"), "super_");
+        assert_eq!(field_name("Self"), "Self_");
+        assert_eq!(field_name("foo.bar"), "foo_bar");
+        assert_eq!(field_name("x-github-event"), "x_github_event");
+        assert_eq!(field_name("1st"), "_1st");
+        assert_eq!(field_name(""), "field");
+        assert_eq!(field_name("-"), "_field");
+    }
+
+    #[test]
+    fn test_type_name() {
+        assert_eq!(type_name("Self".to_owned()), "Self_");
+        assert_eq!(type_name(String::new()), "Empty");
+        assert_eq!(type_name("Open".to_owned()), "Open");
+    }
+}
@@ -1,13 +1,20 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
-use crate::dag::CoDirectedAcyclicGraph;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
+use crate::{dag::CoDirectedAcyclicGraph, visit::Visit};
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct RustComment(pub String);
 
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum RustSegment {
     Struct(RustStruct),
     Enum(RustEnum),
     Alias(RustAlias),
+    Const(RustConst),
 }
 
 impl RustSegment {
@@ -16,11 +23,21 @@ impl RustSegment {
             RustSegment::Struct(s) => &s.name,
             RustSegment::Enum(e) => &e.name,
             RustSegment::Alias(a) => &a.name,
+            RustSegment::Const(c) => &c.name,
+        }
+    }
+    pub fn name_mut(&mut self) -> &mut String {
+        match self {
+            RustSegment::Struct(s) => &mut s.name,
+            RustSegment::Enum(e) => &mut e.name,
+            RustSegment::Alias(a) => &mut a.name,
+            RustSegment::Const(c) => &mut c.name,
         }
     }
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct TypeName {
     pub name: String,
     pub is_borrowed: bool,
@@ -36,20 +53,29 @@ impl TypeName {
 }
 
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum RustType {
     String {
         is_borrowed: bool,
     },
     Number,
+    /// floating point number, only available from JSON Schema `"type": "number"`
+    Float,
     Boolean,
     Custom(TypeName),
     Array(Box<RustType>),
     Map(Box<Self>, Box<Self>),
+    /// tuple of at least one element
+    Tuple(Vec<Self>),
+    /// absent, `null` or value, see [`crate::Opt::tri_state`]
+    Nullable(Box<Self>),
     /// `()`
     #[default]
     Unit,
     Unknown,
     UnknownLiteral,
+    /// literal type of field, lowered to [`RustConst`] unless consumed as tag
+    Literal(LiteralValue),
     UnknownIntersection,
 }
 
@@ -58,27 +84,33 @@ impl RustType {
         match self {
             RustType::String { .. } => "String",
             RustType::Number => "Number",
+            RustType::Float => "Float",
             RustType::Boolean => "Boolean",
             RustType::Custom(c) => &c.name,
-            RustType::Array(t) => t.to_ident(),
+            RustType::Array(t) | RustType::Nullable(t) => t.to_ident(),
             RustType::Unit => "Unit",
             RustType::Unknown => "Unknown",
             RustType::UnknownLiteral => "UnknownLiteral",
+            RustType::Literal(_) => "Literal",
             RustType::UnknownIntersection => "UnknownIntersection",
             RustType::Map(..) => "Map",
+            RustType::Tuple(..) => "Tuple",
         }
     }
 
     pub fn is_unknown(&self) -> bool {
         match &self {
             RustType::UnknownLiteral | RustType::UnknownIntersection => true,
-            RustType::Array(t) => t.is_unknown(),
+            RustType::Array(t) | RustType::Nullable(t) => t.is_unknown(),
             RustType::Map(t1, t2) => t1.is_unknown() || t2.is_unknown(),
+            RustType::Tuple(ts) => ts.iter().any(RustType::is_unknown),
             RustType::Unknown
             | RustType::String { .. }
             | RustType::Number
+            | RustType::Float
             | RustType::Boolean
             | RustType::Custom(_)
+            | RustType::Literal(_)
             | RustType::Unit => false,
         }
     }
@@ -87,13 +119,16 @@ impl RustType {
         match self {
             RustType::String { is_borrowed } => *is_borrowed,
             RustType::Custom(t) => t.is_borrowed,
-            RustType::Array(t) => t.is_borrowed(),
+            RustType::Array(t) | RustType::Nullable(t) => t.is_borrowed(),
             RustType::Map(t1, t2) => t1.is_borrowed() || t2.is_borrowed(),
+            RustType::Tuple(ts) => ts.iter().any(RustType::is_borrowed),
             RustType::Number
+            | RustType::Float
             | RustType::Boolean
             | RustType::Unit
             | RustType::Unknown
             | RustType::UnknownLiteral
+            | RustType::Literal(_)
             | RustType::UnknownIntersection => false,
         }
     }
@@ -131,6 +166,7 @@ impl RustType {
     }
 }
 
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct RustStruct {
     pub attr: RustContainerAttrs,
     pub name: String,
@@ -157,17 +193,23 @@ impl RustStruct {
 
 pub type RustContainerAttrs = Attrs<RustStructAttr>;
 
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum RustStructAttr {
     Serde(SerdeContainerAttr),
+    /// additional derive, e.g. `Clone` or `serde::Serialize`
+    Derive(String),
 }
 
 impl RustStructAttr {
     pub fn as_serde(&self) -> Option<&SerdeContainerAttr> {
-        let Self::Serde(v) = self;
-        Some(v)
+        match self {
+            Self::Serde(v) => Some(v),
+            Self::Derive(_) => None,
+        }
     }
 }
 
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum SerdeContainerAttr {
     RenameAll(RenameRule),
     Tag(String),
@@ -184,18 +226,24 @@ impl SerdeContainerAttr {
     }
 }
 
-#[derive(PartialEq)]
+#[derive(PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum SerdeFieldAttr {
     Rename(String),
     Flatten,
     Borrow,
+    Default,
+    /// path of the predicate, e.g. `Nullable::is_absent`
+    SkipSerializingIf(String),
 }
 
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum SerdeVariantAttr {
     Rename(String),
     Borrow,
 }
 
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum RenameRule {
     PascalCase,
     SnakeCase,
@@ -246,6 +294,19 @@ impl RenameRule {
             _ => unimplemented!(),
         }
     }
+    /// name of the variant `variant` in Pascal case as serde renames it by this rule
+    pub fn apply_to_variant(&self, variant: &str) -> String {
+        let mut s = variant.to_owned();
+        match self {
+            RenameRule::PascalCase => (),
+            RenameRule::SnakeCase => RenameRule::PascalCase.convert_to_snake(&mut s),
+            RenameRule::ScreamingSnakeCase => {
+                RenameRule::PascalCase.convert_to_snake(&mut s);
+                s.make_ascii_uppercase();
+            }
+        }
+        s
+    }
 }
 
 impl ToString for RenameRule {
@@ -259,6 +320,7 @@ impl ToString for RenameRule {
     }
 }
 
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct RustEnum {
     pub attr: RustContainerAttrs,
     pub name: String,
@@ -283,6 +345,8 @@ impl RustEnum {
     }
 }
 
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct RustStructMember {
     pub attr: RustFieldAttrs,
     pub name: String,
@@ -290,9 +354,24 @@ pub struct RustStructMember {
     pub comment: Option<RustComment>,
 }
 
+impl RustStructMember {
+    /// key of the field in JSON
+    pub fn serde_name(&self) -> &str {
+        self.attr
+            .as_inner()
+            .iter()
+            .find_map(|a| match a {
+                RustFieldAttr::Serde(SerdeFieldAttr::Rename(r)) => Some(r.as_str()),
+                _ => None,
+            })
+            .unwrap_or_else(|| crate::ident::unraw(&self.name))
+    }
+}
+
 pub type RustFieldAttrs = Attrs<RustFieldAttr>;
 
-#[derive(Default)]
+#[derive(Default, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Attrs<Field>(Vec<Field>);
 
 impl<T> Attrs<T> {
@@ -317,14 +396,21 @@ impl<T> Attrs<T> {
     }
 }
 
-#[derive(PartialEq)]
+#[derive(PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum RustFieldAttr {
     Serde(SerdeFieldAttr),
 }
 
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct RustMemberType {
     pub ty: RustType,
+    /// may be absent
     pub is_optional: bool,
+    /// may be `null`
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub is_nullable: bool,
 }
 
 impl RustMemberType {
@@ -333,6 +419,7 @@ impl RustMemberType {
     }
 }
 
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct RustEnumMember {
     pub attr: RustVariantAttrs,
     pub kind: RustEnumMemberKind,
@@ -347,6 +434,7 @@ impl From<RustEnumMemberKind> for RustEnumMember {
     }
 }
 
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum RustEnumMemberKind {
     Nullary(String),
     /// has the same ident. this is unary
@@ -355,6 +443,11 @@ pub enum RustEnumMemberKind {
         variant_name: String,
         type_name: RustType,
     },
+    /// fieldless variant of numeric literal union, (de)serialized as its value
+    Discriminant {
+        variant_name: String,
+        value: i64,
+    },
 }
 
 impl RustEnumMemberKind {
@@ -378,6 +471,11 @@ impl RustEnumMemberKind {
         matches!(self, Self::Nullary(..))
     }
 
+    #[must_use]
+    pub fn is_discriminant(&self) -> bool {
+        matches!(self, Self::Discriminant { .. })
+    }
+
     pub fn as_unary(&self) -> Option<&RustType> {
         if let Self::Unary(v) = self {
             Some(v)
@@ -386,9 +484,19 @@ impl RustEnumMemberKind {
         }
     }
 
+    /// name of the generated variant
+    pub fn variant_name(&self) -> &str {
+        match self {
+            RustEnumMemberKind::Nullary(v) => v,
+            RustEnumMemberKind::Unary(t) => t.to_ident(),
+            RustEnumMemberKind::UnaryNamed { variant_name, .. }
+            | RustEnumMemberKind::Discriminant { variant_name, .. } => variant_name,
+        }
+    }
+
     pub fn as_type(&self) -> Option<&RustType> {
         match self {
-            RustEnumMemberKind::Nullary(..) => None,
+            RustEnumMemberKind::Nullary(..) | RustEnumMemberKind::Discriminant { .. } => None,
             RustEnumMemberKind::Unary(t) => Some(t),
             RustEnumMemberKind::UnaryNamed { type_name, .. } => Some(type_name),
         }
@@ -396,7 +504,7 @@ impl RustEnumMemberKind {
 
     pub fn as_type_mut(&mut self) -> Option<&mut RustType> {
         match self {
-            RustEnumMemberKind::Nullary(..) => None,
+            RustEnumMemberKind::Nullary(..) | RustEnumMemberKind::Discriminant { .. } => None,
             RustEnumMemberKind::Unary(t) => Some(t),
             RustEnumMemberKind::UnaryNamed { type_name, .. } => Some(type_name),
         }
@@ -405,10 +513,12 @@ impl RustEnumMemberKind {
 
 pub type RustVariantAttrs = Attrs<RustVariantAttr>;
 
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum RustVariantAttr {
     Serde(SerdeVariantAttr),
 }
 
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct RustAlias {
     pub name: String,
     pub is_borrowed: bool,
@@ -416,8 +526,33 @@ pub struct RustAlias {
     pub ty: RustType,
 }
 
+/// zero-sized type which only accepts `value`
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RustConst {
+    pub name: String,
+    pub value: LiteralValue,
+    pub comment: Option<RustComment>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum LiteralValue {
+    String(String),
+    /// only integers, as floats cannot be matched exactly
+    Number(i64),
+    Boolean(bool),
+}
+
 pub type LiteralKeyMap = HashMap<String, HashMap<String, String>>;
 
+/// generated type name -> dotted path of the schema it came from
+/// (e.g. `IssuesOpenedEvent.issue.user.type`), suffixed by `#n` if numbered
+pub type Origins = HashMap<String, String>;
+
+/// declared or generated type name -> why it is not generated,
+/// e.g. unsupported by the frontend
+pub type Dropped = BTreeMap<String, String>;
+
 pub fn type_deps(segments: &[RustSegment]) -> CoDirectedAcyclicGraph<usize> {
     let index_map: HashMap<_, _> = segments
         .iter()
@@ -426,32 +561,29 @@ pub fn type_deps(segments: &[RustSegment]) -> CoDirectedAcyclicGraph<usize> {
         .collect();
     let mut type_deps = CoDirectedAcyclicGraph::new();
     for (i, segment) in segments.iter().enumerate() {
-        let children: Vec<_> = match segment {
-            RustSegment::Struct(s) => s
-                .member
-                .iter()
-                .flat_map(|m| m.ty.ty.get_using())
-                .map(|t| t.name.as_str())
-                .collect(),
-            RustSegment::Enum(e) => e
-                .member
-                .iter()
-                .flat_map(|m| m.kind.as_type())
-                .flat_map(|m| m.as_custom())
-                .map(|tn| tn.name.as_str())
-                .collect(),
-            RustSegment::Alias(a) => {
-                a.ty.get_using()
-                    .map(|t| t.name.as_str())
-                    .into_iter()
-                    .collect()
-            }
-        };
-        for child in children {
-            if let Some(to) = index_map.get(child) {
+        type_deps.add_node(i);
+        for child in type_names(segment) {
+            if let Some(to) = index_map.get(child.as_str()) {
                 type_deps.add_edge(i, *to);
             }
         }
     }
     type_deps
 }
+
+/// names of types used in the segment
+pub fn type_names(segment: &RustSegment) -> Vec<String> {
+    let mut names = TypeNames::default();
+    names.visit_segment(segment);
+    names.0
+}
+
+/// names of types used in the visited node
+#[derive(Default)]
+struct TypeNames(Vec<String>);
+
+impl Visit for TypeNames {
+    fn visit_type_name(&mut self, type_name: &TypeName) {
+        self.0.push(type_name.name.to_owned());
+    }
+}
@@ -1,14 +1,25 @@
+mod accessors;
 pub mod case;
 mod dag;
+pub mod diff;
 mod frontend;
+mod ident;
 pub mod ir;
+pub mod overrides;
+pub mod report;
+pub mod split;
 mod to_tokens;
 mod transformer;
+pub mod visit;
 
-use frontend::FrontendState;
+use frontend::{json_schema, openapi, FrontendState};
 use std::{
+    cell::RefCell,
     collections::HashMap,
+    fs::File,
+    io::BufReader,
     path::{Path, PathBuf},
+    rc::Rc,
 };
 
 use swc_common::{
@@ -20,9 +31,174 @@ use swc_common::{
 
 use swc_ecma_parser::{lexer::Lexer, Capturing, Parser, StringInput, Syntax};
 
-use ir::{type_deps, LiteralKeyMap, RustAlias, RustSegment, RustType, TypeName};
+use ir::{type_deps, Dropped, LiteralKeyMap, Origins, RustAlias, RustSegment, RustType};
+
+/// output of frontend
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Ir {
+    pub segments: Vec<RustSegment>,
+    /// candidate for discriminated union using literal
+    /// type name -> prop name -> literal value
+    pub literal_key_map: LiteralKeyMap,
+    /// names of the types created by the frontend -> path they came from
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub origins: Origins,
+    /// types left out of the generated code
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub dropped: Dropped,
+}
+
+/// transformer pass defined by user
+///
+/// passes run after built-in transformers except borrowing, in the order of registration.
+/// see also [`Stage::Borrow`].
+pub trait Pass {
+    fn run(&mut self, segments: &mut Vec<RustSegment>);
+}
+
+impl<F: FnMut(&mut Vec<RustSegment>)> Pass for F {
+    fn run(&mut self, segments: &mut Vec<RustSegment>) {
+        self(segments)
+    }
+}
+
+/// stages of the pipeline from frontend to backend
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum Stage {
+    Frontend,
+    InternalTag,
+    RenameAll,
+    /// merge intersections of plain structs, inline single flatten fields
+    /// and apply [`Opt::tri_state`]
+    Flatten,
+    /// merge structurally identical types if [`Opt::dedup`]
+    Dedup,
+    /// user passes run prior to borrowing, unless [`Opt::owned`]
+    Borrow,
+}
+
+impl Stage {
+    pub const ALL: [Stage; 6] = [
+        Stage::Frontend,
+        Stage::InternalTag,
+        Stage::RenameAll,
+        Stage::Flatten,
+        Stage::Dedup,
+        Stage::Borrow,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Stage::Frontend => "frontend",
+            Stage::InternalTag => "internal_tag",
+            Stage::RenameAll => "rename_all",
+            Stage::Flatten => "flatten",
+            Stage::Dedup => "dedup",
+            Stage::Borrow => "borrow",
+        }
+    }
+}
+
+/// IR with the stage that produced it
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct IrSnapshot<I = Ir> {
+    pub stage: Stage,
+    pub ir: I,
+}
+
+pub type Inspect = Box<dyn FnMut(Stage, &Ir)>;
+
+#[derive(Default)]
+pub struct Opt {
+    pub passes: Vec<Box<dyn Pass>>,
+    /// called with the IR after each stage
+    pub inspect: Option<Inspect>,
+    /// distinguish absent fields from `null` ones by `Nullable<T>`
+    /// where a field is both optional and nullable (e.g. `a?: T | null`).
+    /// otherwise both are folded into `Option<T>`.
+    pub tri_state: bool,
+    /// generate owned types (e.g. `String`) instead of borrowing from the input (e.g. `&'a str`)
+    pub owned: bool,
+    /// traits derived by generated structs and enums in addition to `Debug` and `Deserialize`,
+    /// e.g. `Clone` or `serde::Serialize`
+    pub derive: Vec<String>,
+    /// merge types which are identical to another one but named after a different path.
+    /// off by default, as merging renames and removes public types
+    pub dedup: bool,
+    /// names overriding generated ones, applied after all stages
+    pub overrides: Option<overrides::Overrides>,
+    /// entries of [`Opt::overrides`] which matched nothing in the last generation
+    pub unmatched_overrides: Vec<overrides::Unmatched>,
+}
+
+impl Opt {
+    pub fn add_pass(&mut self, pass: impl Pass + 'static) -> &mut Self {
+        self.passes.push(Box::new(pass));
+        self
+    }
+
+    fn inspect(&mut self, stage: Stage, ir: &Ir) {
+        if let Some(inspect) = &mut self.inspect {
+            inspect(stage, ir);
+        }
+    }
+}
 
 pub fn dts2rs(dts_file: &PathBuf) -> proc_macro2::TokenStream {
+    ir2rs(dts2ir(dts_file), &mut Opt::default())
+}
+
+/// generate types from JSON Schema (e.g. `schema.json` of octokit/webhooks)
+pub fn jsonschema2rs(schema_file: &PathBuf) -> proc_macro2::TokenStream {
+    ir2rs(jsonschema2ir(schema_file), &mut Opt::default())
+}
+
+/// generate types for the selected component schemas (and the schemas they refer to)
+/// of OpenAPI 3 description (e.g. `api.github.com.json` of github/rest-api-description)
+pub fn openapi2rs(openapi_file: &PathBuf, schemas: &[&str]) -> proc_macro2::TokenStream {
+    ir2rs(openapi2ir(openapi_file, schemas), &mut Opt::default())
+}
+
+/// generate types from `schema.d.ts` split into modules per event,
+/// leaving out events `enabled` rejects. see [`split`].
+pub fn dts2modules(
+    dts_file: &PathBuf,
+    mut opt: Opt,
+    enabled: impl Fn(&str) -> bool + 'static,
+) -> split::EventModules {
+    // owner of each remaining segment by position, as overrides rename types afterwards
+    let owners = Rc::new(RefCell::new(Vec::new()));
+    let pass_owners = owners.clone();
+    opt.add_pass(move |segments: &mut Vec<RustSegment>| {
+        let owners = split::event_owners(segments, "Schema");
+        split::exclude_events(segments, &owners, &enabled);
+        *pass_owners.borrow_mut() = segments
+            .iter()
+            .map(|s| owners.get(s.name()).cloned())
+            .collect::<Vec<_>>();
+    });
+    let mut ir = dts2ir(dts_file);
+    opt.inspect(Stage::Frontend, &ir);
+    transform(Stage::Frontend, &mut ir, &mut opt);
+    let accessors = accessors::accessors(&ir.segments, "Schema");
+    let owners: split::Owners = ir
+        .segments
+        .iter()
+        .zip(owners.borrow().iter())
+        .filter_map(|(s, event)| Some((s.name().to_owned(), event.clone()?)))
+        .collect();
+    let mut modules = split::split_events(ir.segments, &owners);
+    modules.accessors = accessors;
+    modules
+}
+
+pub fn dts2ir(dts_file: &PathBuf) -> Ir {
     let ExtractedModule { module, comments } = extract_module(dts_file);
 
     let mut segments = Vec::new();
@@ -31,11 +207,12 @@ pub fn dts2rs(dts_file: &PathBuf) -> proc_macro2::TokenStream {
         segments: &mut segments,
         comments: &comments,
         name_types: Default::default(),
+        utility_types: Default::default(),
+        origins: Default::default(),
     };
 
-    // candidate for discriminated union using literal
-    // type name -> prop name -> literal value
     let mut lkm: LiteralKeyMap = HashMap::new();
+    let mut dropped = Dropped::new();
 
     for b in &module.body {
         let b = b.as_module_decl().unwrap();
@@ -61,9 +238,9 @@ pub fn dts2rs(dts_file: &PathBuf) -> proc_macro2::TokenStream {
                 if ident == "WebhookEvents" {
                     st.segments.push(RustSegment::Alias(RustAlias {
                         name: "WebhookEvents".to_owned(),
-                        is_borrowed: true,
+                        is_borrowed: false,
                         comment,
-                        ty: RustType::Array(Box::new(RustType::String { is_borrowed: true })),
+                        ty: RustType::Array(Box::new(RustType::String { is_borrowed: false })),
                     }));
                     continue; //return Err(anyhow!("lazy skip"));
                 }
@@ -71,18 +248,7 @@ pub fn dts2rs(dts_file: &PathBuf) -> proc_macro2::TokenStream {
                 let typ = &talias.type_ann;
                 match typ.as_ref() {
                     swc_ecma_ast::TsType::TsTypeRef(tref) => {
-                        let rhs = tref.type_name.as_ident().unwrap().sym.as_ref();
-                        let rhs = rhs.to_owned();
-                        let a = RustSegment::Alias(RustAlias {
-                            name: ident.to_owned(),
-                            is_borrowed: false,
-                            comment,
-                            ty: RustType::Custom(TypeName {
-                                name: rhs,
-                                is_borrowed: false,
-                            }),
-                        });
-                        st.segments.push(a);
+                        frontend::talias_type_ref(&mut st, ident, tref, comment, &mut lkm);
                     }
                     swc_ecma_ast::TsType::TsUnionOrIntersectionType(tuoi) => {
                         frontend::tunion2enum(&mut st, ident, tuoi, comment, &mut lkm, true);
@@ -103,6 +269,7 @@ pub fn dts2rs(dts_file: &PathBuf) -> proc_macro2::TokenStream {
                     swc_ecma_ast::TsType::TsTypeOperator(_toperator) => {
                         // export type WebhookEventName = keyof EventPayloadMap;
                         //dbg!(toperator);
+                        dropped.insert(ident.to_owned(), "unsupported type operator".to_owned());
                         continue;
                     }
                     _ => {
@@ -115,20 +282,175 @@ pub fn dts2rs(dts_file: &PathBuf) -> proc_macro2::TokenStream {
         };
         //println!("{}", b.is_export_decl());
     }
-    // drop(st);
+    let utility_types = std::mem::take(&mut st.utility_types);
+    let mut origins = std::mem::take(&mut st.origins);
+    frontend::utility_types::resolve(&mut segments, &mut origins, utility_types);
 
-    for segment in &mut segments {
-        transformer::adapt_internal_tag(segment, &lkm);
-        transformer::adapt_rename_all(segment);
+    ident::dedup_fields(&mut segments);
+    Ir {
+        segments,
+        literal_key_map: lkm,
+        origins,
+        dropped,
     }
-    transformer::flatten_type(&mut segments);
-    let type_deps = type_deps(&segments);
-    transformer::adapt_borrow(&mut segments, &type_deps);
+}
+
+pub fn jsonschema2ir(schema_file: &PathBuf) -> Ir {
+    let schema = load_json(schema_file);
+
+    let (ref_prefix, definitions) = match (schema.get("definitions"), schema.get("$defs")) {
+        (Some(defs), _) => ("#/definitions/", defs),
+        (None, Some(defs)) => ("#/$defs/", defs),
+        (None, None) => panic!("JSON Schema has no definitions"),
+    };
+    let definitions = definitions.as_object().expect("definitions is object");
+    let names = json_schema::definition_names(definitions);
+    let root_name = json_schema::definition_name("Schema", &schema);
+
+    let mut segments = Vec::new();
+    let comments = Default::default();
 
-    segments
-        .into_iter()
-        .flat_map(|rss| rss.into_token_stream())
-        .collect()
+    let mut st = FrontendState {
+        segments: &mut segments,
+        comments: &comments,
+        name_types: Default::default(),
+        utility_types: Default::default(),
+        origins: Default::default(),
+    };
+    let jst = json_schema::JsonSchemaState::new(ref_prefix, definitions, &names);
+
+    let mut lkm: LiteralKeyMap = HashMap::new();
+
+    if ["oneOf", "anyOf", "properties"]
+        .iter()
+        .any(|k| schema.get(k).is_some())
+    {
+        json_schema::definition2segment(&mut st, &jst, &root_name, &schema, &mut lkm);
+    }
+    for (key, definition) in definitions {
+        json_schema::definition2segment(&mut st, &jst, &names[key], definition, &mut lkm);
+    }
+    let origins = std::mem::take(&mut st.origins);
+
+    ident::dedup_fields(&mut segments);
+    Ir {
+        segments,
+        literal_key_map: lkm,
+        origins,
+        dropped: Dropped::new(),
+    }
+}
+
+pub fn openapi2ir(openapi_file: &PathBuf, schemas: &[&str]) -> Ir {
+    let openapi = load_json(openapi_file);
+    let components = openapi::component_schemas(&openapi);
+    let names = openapi::component_names(components);
+
+    let mut segments = Vec::new();
+    let comments = Default::default();
+
+    let mut st = FrontendState {
+        segments: &mut segments,
+        comments: &comments,
+        name_types: Default::default(),
+        utility_types: Default::default(),
+        origins: Default::default(),
+    };
+    let jst = json_schema::JsonSchemaState::new(openapi::REF_PREFIX, components, &names);
+
+    let mut lkm: LiteralKeyMap = HashMap::new();
+
+    for key in openapi::select_schemas(components, schemas) {
+        json_schema::definition2segment(&mut st, &jst, &names[key], &components[key], &mut lkm);
+    }
+    let origins = std::mem::take(&mut st.origins);
+
+    ident::dedup_fields(&mut segments);
+    Ir {
+        segments,
+        literal_key_map: lkm,
+        origins,
+        dropped: Dropped::new(),
+    }
+}
+
+fn load_json(file: &PathBuf) -> serde_json::Value {
+    let f = File::open(file).unwrap_or_else(|_| panic!("failed to load {}", &file.display()));
+    serde_json::from_reader(BufReader::new(f)).expect("Failed to parse JSON.")
+}
+
+/// apply transformers to segments obtained from frontend and generate tokens
+pub fn ir2rs(ir: Ir, opt: &mut Opt) -> proc_macro2::TokenStream {
+    opt.inspect(Stage::Frontend, &ir);
+    resume(Stage::Frontend, ir, opt)
+}
+
+/// apply transformers following `done`, the stage which produced `ir`, and generate tokens
+pub fn resume(done: Stage, mut ir: Ir, opt: &mut Opt) -> proc_macro2::TokenStream {
+    transform(done, &mut ir, opt);
+    let accessors = accessors::accessors(&ir.segments, "Schema");
+    let mut tokens = segments2rs(ir.segments);
+    tokens.extend(accessors);
+    tokens
+}
+
+/// apply transformers following `done`, the stage which produced `ir`, overrides and derives
+pub fn transform(done: Stage, ir: &mut Ir, opt: &mut Opt) {
+    for stage in Stage::ALL.into_iter().filter(|s| done < *s) {
+        let Ir {
+            segments,
+            literal_key_map: lkm,
+            origins,
+            ..
+        } = ir;
+        match stage {
+            Stage::Frontend => unreachable!(),
+            Stage::InternalTag => {
+                transformer::adapt_internal_tag(segments, lkm);
+                transformer::adapt_literal_const(segments);
+            }
+            Stage::RenameAll => {
+                transformer::adapt_rename_all(segments);
+            }
+            Stage::Flatten => {
+                transformer::merge_intersection(segments);
+                transformer::flatten_type(segments);
+                if opt.tri_state {
+                    transformer::adapt_tri_state(segments);
+                }
+            }
+            Stage::Dedup => {
+                if opt.dedup {
+                    transformer::dedup_types(segments, origins);
+                }
+            }
+            Stage::Borrow => {
+                for pass in &mut opt.passes {
+                    pass.run(segments);
+                }
+                if !opt.owned {
+                    let type_deps = type_deps(segments);
+                    transformer::adapt_borrow(segments, &type_deps);
+                }
+            }
+        }
+        opt.inspect(stage, ir);
+    }
+    if let Some(overrides) = &opt.overrides {
+        opt.unmatched_overrides = overrides.apply(&mut ir.segments, &ir.origins);
+    }
+    transformer::add_derives(&mut ir.segments, &opt.derive);
+}
+
+/// generate tokens of transformed segments
+pub fn segments2rs(segments: Vec<RustSegment>) -> proc_macro2::TokenStream {
+    let mut tokens = if transformer::uses_nullable(&segments) {
+        to_tokens::nullable_definition()
+    } else {
+        proc_macro2::TokenStream::new()
+    };
+    tokens.extend(segments.into_iter().flat_map(|rss| rss.into_token_stream()));
+    tokens
 }
 
 struct ExtractedModule {
@@ -200,4 +522,125 @@ mod tests {
             .as_ref();
         assert_eq!(ice, "IssueCommentCreatedEvent");
     }
+
+    #[test]
+    fn test_json_schema() {
+        let rs = jsonschema2rs(&PathBuf::from("test.schema.json")).to_string();
+
+        assert!(rs.contains("pub enum Schema"));
+        assert!(rs.contains(r#"# [serde (tag = "action")] # [serde (rename_all = "snake_case")] pub enum IssueCommentEvent"#));
+        assert!(rs.contains("pub score : Option < f64 >"));
+        assert!(rs.contains("pub type DateTime < 'a > = & 'a str ;"));
+        assert!(rs.contains("pub created_at : DateTime < 'a > ,"));
+        assert!(rs.contains("pub html_url : Option < Uri < 'a > > ,"));
+    }
+
+    #[test]
+    fn test_pass() {
+        use visit::VisitMut;
+
+        struct Timestamp;
+        impl VisitMut for Timestamp {
+            fn visit_struct_member_mut(&mut self, member: &mut ir::RustStructMember) {
+                if member.name.ends_with("_at") {
+                    member.ty.ty = RustType::Custom(ir::TypeName::new("Timestamp".to_owned()));
+                }
+            }
+        }
+
+        let mut opt = Opt::default();
+        opt.add_pass(|segments: &mut Vec<RustSegment>| {
+            visit::walk_segments_mut(&mut Timestamp, segments)
+        });
+        let rs = ir2rs(jsonschema2ir(&PathBuf::from("test.schema.json")), &mut opt).to_string();
+
+        assert!(rs.contains("pub created_at : Timestamp ,"));
+    }
+
+    #[test]
+    fn test_tri_state() {
+        let rs = ir2rs(dts2ir(&PathBuf::from("test.ts")), &mut Opt::default()).to_string();
+        assert!(!rs.contains("Nullable"));
+
+        let mut opt = Opt {
+            tri_state: true,
+            ..Default::default()
+        };
+        let rs = ir2rs(dts2ir(&PathBuf::from("test.ts")), &mut opt).to_string();
+        assert!(rs.contains("pub enum Nullable"));
+        assert!(rs.contains(
+            "# [serde (default)] # [serde (skip_serializing_if = \"Nullable::is_absent\")] pub email : Nullable < & 'a str > ,"
+        ));
+        assert!(
+            rs.contains("impl < T : serde :: Serialize > serde :: Serialize for Nullable < T >")
+        );
+        assert!(rs.contains("pub url : Option < & 'a str > ,"));
+    }
+
+    #[test]
+    fn test_dts2modules() {
+        let dts = std::env::temp_dir().join(format!("split-{}.d.ts", std::process::id()));
+        std::fs::write(
+            &dts,
+            "export type Schema = PushEvent | PingEvent;
+            export interface PushEvent { commit: Commit; sender: User; }
+            export interface PingEvent { hook: Hook; sender: User; }
+            export interface Commit { message?: string | null; }
+            export interface Hook { id: number; }
+            export interface User { login: string; }",
+        )
+        .unwrap();
+        let opt = Opt {
+            tri_state: true,
+            overrides: Some(overrides::Overrides {
+                types: [("Commit".to_owned(), "PushCommit".to_owned())].into(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let modules = dts2modules(&dts, opt, |_| true);
+        std::fs::remove_file(dts).unwrap();
+
+        let names = |segments: &[RustSegment]| -> Vec<String> {
+            segments.iter().map(|s| s.name().to_owned()).collect()
+        };
+        assert_eq!(names(&modules.events["push"]), ["PushEvent", "PushCommit"]);
+        assert_eq!(names(&modules.shared), ["Schema", "User"]);
+        let rs = modules.into_tokens().to_string();
+        assert!(rs.starts_with("# [doc = \"field which may be absent, `null` or a value\"]"));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_resume_from_snapshot() {
+        use std::{cell::RefCell, rc::Rc};
+
+        let snapshots = Rc::new(RefCell::new(Vec::new()));
+        let mut opt = Opt::default();
+        let s = snapshots.clone();
+        opt.inspect = Some(Box::new(move |stage, ir| {
+            let snapshot = serde_json::to_string(&IrSnapshot { stage, ir }).unwrap();
+            s.borrow_mut().push(snapshot);
+        }));
+        let expected =
+            ir2rs(jsonschema2ir(&PathBuf::from("test.schema.json")), &mut opt).to_string();
+
+        let snapshots = snapshots.take();
+        assert_eq!(snapshots.len(), Stage::ALL.len());
+        for snapshot in snapshots {
+            let IrSnapshot { stage, ir }: IrSnapshot = serde_json::from_str(&snapshot).unwrap();
+            let rs = resume(stage, ir, &mut Opt::default()).to_string();
+            assert_eq!(rs, expected, "resumed from {}", stage.as_str());
+        }
+    }
+
+    #[test]
+    fn test_openapi() {
+        let rs = openapi2rs(&PathBuf::from("test.openapi.json"), &["issue"]).to_string();
+
+        assert!(rs.contains("pub struct Issue"));
+        assert!(rs.contains("pub user : Option < NullableSimpleUser < 'a > >"));
+        assert!(rs.contains("pub struct SimpleUser"));
+        assert!(!rs.contains("RateLimit"));
+    }
 }
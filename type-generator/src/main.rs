@@ -1,17 +1,422 @@
-use std::path::PathBuf;
+use std::{
+    fs::File,
+    io::{BufWriter, Read, Write},
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
 
 use structopt::StructOpt;
 
-use github_webhook_type_generator::*;
+use github_webhook_type_generator::{report::Report, *};
 
 #[derive(Debug, StructOpt)]
-struct Opt {
-    dts_file: PathBuf,
+enum Opt {
+    /// generate Rust types
+    Generate(Generate),
+    /// download `schema.d.ts` of octokit/webhooks
+    Download(Download),
+    /// list types the generated code does not express: unknown field types and dropped types
+    Report(ReportCmd),
+    /// write the intermediate representation at a stage as JSON
+    Ir(IrCmd),
+    /// list changes of generated types between two schemas, e.g. before and after upgrade
+    Diff(DiffCmd),
+    /// decide the version bump changes of generated types require under Rust semver rules
+    Semver(SemverCmd),
+}
+
+#[derive(Debug, StructOpt)]
+struct Input {
+    /// `schema.d.ts`, or `schema.json` for JSON Schema
+    #[structopt(required_unless = "from-ir")]
+    dts_file: Option<PathBuf>,
+    /// treat input as OpenAPI description and generate the given component schemas
+    #[structopt(long = "openapi-schema")]
+    openapi_schemas: Vec<String>,
+    /// start from IR written by `ir` or `--dump-ir`
+    #[structopt(long, conflicts_with = "dts-file")]
+    from_ir: Option<PathBuf>,
+}
+
+#[derive(Debug, StructOpt)]
+struct Transform {
+    /// generate `Nullable<T>` for fields which may be both absent and `null`
+    #[structopt(long)]
+    tri_state: bool,
+    /// generate owned types instead of borrowing from the input
+    #[structopt(long)]
+    owned: bool,
+    /// merge structurally identical types, renaming and removing some
+    #[structopt(long)]
+    dedup: bool,
+    /// TOML file renaming generated types, fields and variants
+    #[structopt(long)]
+    overrides: Option<PathBuf>,
+    /// fail if some overrides match nothing
+    #[structopt(long, requires = "overrides")]
+    deny_unmatched_overrides: bool,
+}
+
+#[derive(Debug, StructOpt)]
+struct Generate {
+    #[structopt(flatten)]
+    input: Input,
+    #[structopt(flatten)]
+    transform: Transform,
+    /// additional trait to derive for every struct and enum, e.g. `Clone`
+    #[structopt(long)]
+    derive: Vec<String>,
+    /// format the output by rustfmt
+    #[structopt(long)]
+    format: bool,
+    /// write into the file instead of stdout
+    #[structopt(short, long)]
+    output: Option<PathBuf>,
+    /// write IR after each stage into the directory, as `<n>-<stage>.json`
+    #[structopt(long)]
+    dump_ir: Option<PathBuf>,
+}
+
+#[derive(Debug, StructOpt)]
+struct Download {
+    /// branch, tag or commit of octokit/webhooks
+    #[structopt(long, default_value = "main")]
+    version: String,
+    /// directory caching schemas per version,
+    /// default: `$XDG_CACHE_HOME/github-webhook-rs` or `~/.cache/github-webhook-rs`
+    #[structopt(long)]
+    cache_dir: Option<PathBuf>,
+    /// download even if the version is cached
+    #[structopt(long)]
+    force: bool,
+    /// copy the schema into the file, otherwise print the path of the cached one
+    #[structopt(short, long)]
+    output: Option<PathBuf>,
+}
+
+#[derive(Debug, StructOpt)]
+struct ReportCmd {
+    #[structopt(flatten)]
+    input: Input,
+    #[structopt(flatten)]
+    transform: Transform,
+    /// fail if some fields have unknown types
+    #[structopt(long)]
+    deny_unknown: bool,
+}
+
+#[derive(Debug, StructOpt)]
+struct IrCmd {
+    #[structopt(flatten)]
+    input: Input,
+    #[structopt(flatten)]
+    transform: Transform,
+    /// stage after which the IR is written: frontend, internal_tag, rename_all, flatten, dedup
+    /// or borrow
+    #[structopt(long, default_value = "borrow", parse(try_from_str = parse_stage))]
+    stage: Stage,
+    /// write into the file instead of stdout
+    #[structopt(short, long)]
+    output: Option<PathBuf>,
+}
+
+#[derive(Debug, StructOpt)]
+struct Compare {
+    /// schema before the change
+    old: PathBuf,
+    /// schema after the change
+    new: PathBuf,
+    /// treat inputs as OpenAPI descriptions and compare the given component schemas
+    #[structopt(long = "openapi-schema")]
+    openapi_schemas: Vec<String>,
+    /// inputs are IR written by `ir` or `--dump-ir`, e.g. kept from the previous release
+    #[structopt(long, conflicts_with = "openapi-schema")]
+    ir: bool,
+    #[structopt(flatten)]
+    transform: Transform,
+}
+
+#[derive(Debug, StructOpt)]
+struct DiffCmd {
+    #[structopt(flatten)]
+    compare: Compare,
+    /// fail if some changes are breaking
+    #[structopt(long)]
+    deny_breaking: bool,
+}
+
+#[derive(Debug, StructOpt)]
+struct SemverCmd {
+    #[structopt(flatten)]
+    compare: Compare,
+    /// version released with the old schema, e.g. `0.6.0`
+    #[structopt(long)]
+    released: Option<String>,
+    /// version to release with the new schema. fail if it is less than required
+    #[structopt(long, requires = "released")]
+    planned: Option<String>,
 }
 
 fn main() {
-    let opt = Opt::from_args();
+    match Opt::from_args() {
+        Opt::Generate(cmd) => generate(cmd),
+        Opt::Download(cmd) => download(cmd),
+        Opt::Report(cmd) => report(cmd),
+        Opt::Ir(cmd) => ir(cmd),
+        Opt::Diff(cmd) => diff(cmd),
+        Opt::Semver(cmd) => semver(cmd),
+    }
+}
+
+fn generate(cmd: Generate) {
+    let mut gen_opt = cmd.transform.to_opt();
+    gen_opt.derive = cmd.derive;
+    if let Some(dir) = cmd.dump_ir {
+        std::fs::create_dir_all(&dir).expect("failed to create directory");
+        gen_opt.inspect = Some(Box::new(move |stage, ir| {
+            let path = dir.join(format!("{}-{}.json", stage as usize, stage.as_str()));
+            let writer = BufWriter::new(File::create(path).expect("failed to create IR file"));
+            write_ir(writer, stage, ir);
+        }));
+    }
+
+    let (done, ir) = cmd.input.load();
+    let rs = match done {
+        Stage::Frontend => ir2rs(ir, &mut gen_opt),
+        _ => resume(done, ir, &mut gen_opt),
+    };
+    cmd.transform.check_overrides(&gen_opt);
+
+    let mut rs = rs.to_string();
+    if cmd.format {
+        rs = rustfmt(&rs);
+    }
+    write!(output(cmd.output.as_deref()), "{rs}").expect("failed to write output");
+}
+
+fn download(cmd: Download) {
+    let cache_dir = cmd
+        .cache_dir
+        .or_else(|| {
+            std::env::var_os("XDG_CACHE_HOME")
+                .map(PathBuf::from)
+                .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))
+                .map(|dir| dir.join("github-webhook-rs"))
+        })
+        .expect("no cache directory, specify `--cache-dir`");
+    // branches may contain `/`
+    let dir = cache_dir.join(cmd.version.replace('/', "%2F"));
+    let cached = dir.join("schema.d.ts");
+
+    if cmd.force || !cached.is_file() {
+        std::fs::create_dir_all(&dir).expect("failed to create cache directory");
+        github_webhook_dts_downloader::download_dts(github_webhook_dts_downloader::Opt {
+            version: github_webhook_dts_downloader::Version(cmd.version),
+            out_path_ts: github_webhook_dts_downloader::OutPathTs(cached.clone()),
+        })
+        .expect("failed to download schema");
+    }
+
+    match cmd.output {
+        Some(path) => {
+            std::fs::copy(&cached, path).expect("failed to copy schema");
+        }
+        None => println!("{}", cached.display()),
+    }
+}
+
+fn report(cmd: ReportCmd) {
+    let mut gen_opt = cmd.transform.to_opt();
+    let (done, mut ir) = cmd.input.load();
+    transform(done, &mut ir, &mut gen_opt);
+    cmd.transform.check_overrides(&gen_opt);
+
+    let report = Report::new(&ir);
+    print!("{report}");
+    if cmd.deny_unknown && !report.unknown.is_empty() {
+        std::process::exit(1);
+    }
+}
+
+fn ir(cmd: IrCmd) {
+    let mut gen_opt = cmd.transform.to_opt();
+    let (done, mut ir) = cmd.input.load();
+    if done > cmd.stage {
+        panic!(
+            "the IR is produced by `{}`, after `{}`",
+            done.as_str(),
+            cmd.stage.as_str()
+        );
+    }
+    if done == cmd.stage {
+        write_ir(output(cmd.output.as_deref()), done, &ir);
+        return;
+    }
+
+    let mut writer = Some(output(cmd.output.as_deref()));
+    let target = cmd.stage;
+    gen_opt.inspect = Some(Box::new(move |stage, ir| {
+        if stage == target {
+            if let Some(writer) = writer.take() {
+                write_ir(writer, stage, ir);
+            }
+        }
+    }));
+    transform(done, &mut ir, &mut gen_opt);
+}
+
+fn diff(cmd: DiffCmd) {
+    let changes = cmd.compare.changes();
+    for change in &changes {
+        println!("{change}");
+    }
+    let breaking = changes.iter().filter(|c| c.is_breaking()).count();
+    println!("{} changes, {} breaking", changes.len(), breaking);
+    if cmd.deny_breaking && breaking > 0 {
+        std::process::exit(1);
+    }
+}
+
+fn semver(cmd: SemverCmd) {
+    let changes = cmd.compare.changes();
+    let bump = diff::required_bump(&changes);
+    for change in changes.iter().filter(|c| c.bump() == bump) {
+        println!("{change}");
+    }
+    println!("required bump: {}", bump.as_str());
+
+    let Some(released) = cmd.released else {
+        return;
+    };
+    let next = bump.apply(&released).expect("invalid released version");
+    println!("next version: {next}");
+    if let Some(planned) = cmd.planned {
+        if !diff::is_compatible(&released, &planned, &changes).expect("invalid planned version") {
+            eprintln!("error: {planned} is less than {next}");
+            std::process::exit(1);
+        }
+    }
+}
+
+impl Compare {
+    /// changes of the transformed IR from `old` to `new`
+    fn changes(&self) -> Vec<diff::Change> {
+        let [old, new] = [&self.old, &self.new].map(|file| {
+            let mut gen_opt = self.transform.to_opt();
+            let (done, mut ir) = match self.ir {
+                true => load_snapshot(file),
+                false => (Stage::Frontend, load_schema(file, &self.openapi_schemas)),
+            };
+            transform(done, &mut ir, &mut gen_opt);
+            self.transform.check_overrides(&gen_opt);
+            ir
+        });
+        diff::diff(&old.segments, &new.segments)
+    }
+}
+
+impl Input {
+    /// IR and the stage which produced it
+    fn load(&self) -> (Stage, Ir) {
+        if let Some(from_ir) = &self.from_ir {
+            return load_snapshot(from_ir);
+        }
+        let dts_file = self.dts_file.as_ref().unwrap();
+        (
+            Stage::Frontend,
+            load_schema(dts_file, &self.openapi_schemas),
+        )
+    }
+}
+
+fn load_snapshot(file: &PathBuf) -> (Stage, Ir) {
+    let reader = std::io::BufReader::new(File::open(file).expect("failed to open IR file"));
+    let IrSnapshot { stage, ir }: IrSnapshot =
+        serde_json::from_reader(reader).expect("failed to read IR");
+    (stage, ir)
+}
+
+/// run the frontend for the file type
+fn load_schema(file: &PathBuf, openapi_schemas: &[String]) -> Ir {
+    match file.extension().and_then(|e| e.to_str()) {
+        Some("json") if !openapi_schemas.is_empty() => {
+            let schemas: Vec<&str> = openapi_schemas.iter().map(String::as_str).collect();
+            openapi2ir(file, &schemas)
+        }
+        Some("json") => jsonschema2ir(file),
+        _ => dts2ir(file),
+    }
+}
+
+impl Transform {
+    fn to_opt(&self) -> github_webhook_type_generator::Opt {
+        let mut gen_opt = github_webhook_type_generator::Opt {
+            tri_state: self.tri_state,
+            owned: self.owned,
+            dedup: self.dedup,
+            ..Default::default()
+        };
+        if let Some(path) = &self.overrides {
+            let s = std::fs::read_to_string(path).expect("failed to read overrides");
+            gen_opt.overrides =
+                Some(overrides::Overrides::from_toml(&s).expect("invalid overrides"));
+        }
+        gen_opt
+    }
+
+    /// warn overrides which matched nothing, and exit if denied
+    fn check_overrides(&self, gen_opt: &github_webhook_type_generator::Opt) {
+        for unmatched in &gen_opt.unmatched_overrides {
+            eprintln!("warning: override {unmatched}");
+        }
+        if self.deny_unmatched_overrides && !gen_opt.unmatched_overrides.is_empty() {
+            std::process::exit(1);
+        }
+    }
+}
+
+fn parse_stage(s: &str) -> Result<Stage, String> {
+    Stage::ALL
+        .into_iter()
+        .find(|stage| stage.as_str() == s)
+        .ok_or_else(|| format!("unknown stage `{s}`"))
+}
+
+fn output(path: Option<&Path>) -> Box<dyn Write> {
+    match path {
+        Some(path) => Box::new(BufWriter::new(
+            File::create(path).expect("failed to create output file"),
+        )),
+        None => Box::new(std::io::stdout().lock()),
+    }
+}
+
+fn write_ir(writer: impl Write, stage: Stage, ir: &Ir) {
+    serde_json::to_writer_pretty(writer, &IrSnapshot { stage, ir }).expect("failed to write IR");
+}
 
-    let rs = dts2rs(&opt.dts_file);
-    print!("{}", rs);
+fn rustfmt(rs: &str) -> String {
+    let mut child = Command::new("rustfmt")
+        .args(["--edition", "2021"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("failed to execute rustfmt");
+    let mut stdin = child.stdin.take().unwrap();
+    let rs = rs.to_owned();
+    // write concurrently, or rustfmt may block on full stdout
+    let writer = std::thread::spawn(move || stdin.write_all(rs.as_bytes()));
+    let mut formatted = String::new();
+    child
+        .stdout
+        .take()
+        .unwrap()
+        .read_to_string(&mut formatted)
+        .expect("failed to read rustfmt output");
+    writer.join().unwrap().expect("failed to write to rustfmt");
+    let status = child.wait().expect("failed to wait rustfmt");
+    if !status.success() {
+        panic!("failed to execute rustfmt: {status}");
+    }
+    formatted
 }
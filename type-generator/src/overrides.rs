@@ -0,0 +1,327 @@
+//! user-provided names for generated types, fields and variants
+//!
+//! ```toml
+//! [types]
+//! # generated name, or the path it came from (see `Ir::origins`)
+//! "IssuesOpenedEvent.issue.user.type" = "UserKind"
+//!
+//! [fields]
+//! # `<type>.<field>`, where field is the JSON key or the generated field name
+//! "Repository.private" = "is_private"
+//!
+//! [variants]
+//! # `<type>.<variant>`, where variant is the generated variant name
+//! "UserKind.Bot" = "Robot"
+//! ```
+//!
+//! types in the keys of `fields` and `variants` refer to the names before overriding.
+
+use std::{collections::BTreeMap, fmt};
+
+use crate::{
+    ident,
+    ir::{
+        Origins, RustEnum, RustEnumMemberKind, RustFieldAttr, RustSegment, RustStructAttr,
+        RustStructMember, RustType, RustVariantAttr, SerdeContainerAttr, SerdeFieldAttr,
+        SerdeVariantAttr, TypeName,
+    },
+    visit::{self, VisitMut},
+};
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize),
+    serde(default, deny_unknown_fields)
+)]
+pub struct Overrides {
+    pub types: BTreeMap<String, String>,
+    pub fields: BTreeMap<String, String>,
+    pub variants: BTreeMap<String, String>,
+}
+
+/// entry of [`Overrides`] which matches nothing, e.g. after a schema update
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Unmatched {
+    /// `types`, `fields` or `variants`
+    pub table: &'static str,
+    pub key: String,
+}
+
+impl fmt::Display for Unmatched {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {:?} matches nothing", self.table, self.key)
+    }
+}
+
+impl Overrides {
+    #[cfg(feature = "serde")]
+    pub fn from_toml(s: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(s)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.types.is_empty() && self.fields.is_empty() && self.variants.is_empty()
+    }
+
+    /// rename types, fields and variants keeping their (de)serialized form,
+    /// and return the entries which match nothing
+    pub fn apply(&self, segments: &mut [RustSegment], origins: &Origins) -> Vec<Unmatched> {
+        let mut unmatched = Vec::new();
+
+        for (key, new) in &self.fields {
+            let matched = split_key(key).map_or(false, |(ty, field)| {
+                let mut matched = false;
+                for name in resolve(segments, origins, ty) {
+                    for segment in segments.iter_mut() {
+                        if let RustSegment::Struct(s) = segment {
+                            if s.name == name {
+                                matched |= rename_field(&mut s.member, field, new);
+                            }
+                        }
+                    }
+                }
+                matched
+            });
+            if !matched {
+                unmatched.push(Unmatched {
+                    table: "fields",
+                    key: key.to_owned(),
+                });
+            }
+        }
+
+        for (key, new) in &self.variants {
+            let matched = split_key(key).map_or(false, |(ty, variant)| {
+                let mut matched = false;
+                for name in resolve(segments, origins, ty) {
+                    for segment in segments.iter_mut() {
+                        if let RustSegment::Enum(e) = segment {
+                            if e.name == name {
+                                matched |= rename_variant(e, variant, new);
+                            }
+                        }
+                    }
+                }
+                matched
+            });
+            if !matched {
+                unmatched.push(Unmatched {
+                    table: "variants",
+                    key: key.to_owned(),
+                });
+            }
+        }
+
+        let mut renames = BTreeMap::new();
+        for (key, new) in &self.types {
+            let names = resolve(segments, origins, key);
+            if names.is_empty() {
+                unmatched.push(Unmatched {
+                    table: "types",
+                    key: key.to_owned(),
+                });
+            }
+            for name in names {
+                renames.insert(name, new.to_owned());
+            }
+        }
+        rename_types(segments, &renames);
+
+        unmatched
+    }
+}
+
+/// `Type.field` -> (`Type`, `field`). the type may be a dotted path.
+fn split_key(key: &str) -> Option<(&str, &str)> {
+    key.rsplit_once('.')
+}
+
+/// names of the types `key` refers to, by name or by path
+fn resolve(segments: &[RustSegment], origins: &Origins, key: &str) -> Vec<String> {
+    if segments.iter().any(|s| s.name() == key) {
+        return vec![key.to_owned()];
+    }
+    let mut names: Vec<String> = origins
+        .iter()
+        .filter(|(name, origin)| {
+            *origin == key && segments.iter().any(|s| s.name() == name.as_str())
+        })
+        .map(|(name, _)| name.to_owned())
+        .collect();
+    names.sort();
+    names
+}
+
+fn rename_field(member: &mut [RustStructMember], field: &str, new: &str) -> bool {
+    let Some(m) = member
+        .iter_mut()
+        .find(|m| m.serde_name() == field || ident::unraw(&m.name) == field)
+    else {
+        return false;
+    };
+    let attrs = m.attr.as_inner();
+    let keeps_key = attrs.iter().any(|a| {
+        matches!(
+            a,
+            RustFieldAttr::Serde(SerdeFieldAttr::Rename(_) | SerdeFieldAttr::Flatten)
+        )
+    });
+    if !keeps_key {
+        let key = m.serde_name().to_owned();
+        m.attr
+            .add_attr(RustFieldAttr::Serde(SerdeFieldAttr::Rename(key)));
+    }
+    m.name = ident::field_name(new);
+    true
+}
+
+fn rename_variant(e: &mut RustEnum, variant: &str, new: &str) -> bool {
+    let rename_all = e.attr.as_inner().iter().find_map(|a| match a {
+        RustStructAttr::Serde(SerdeContainerAttr::RenameAll(rr)) => Some(rr),
+        _ => None,
+    });
+    let Some(m) = e
+        .member
+        .iter_mut()
+        .find(|m| m.kind.variant_name() == variant)
+    else {
+        return false;
+    };
+    // numeric variants are (de)serialized by value
+    let has_rename = m
+        .attr
+        .as_inner()
+        .iter()
+        .any(|a| matches!(a, RustVariantAttr::Serde(SerdeVariantAttr::Rename(_))));
+    if !m.kind.is_discriminant() && !has_rename {
+        let wire = match rename_all {
+            Some(rr) => rr.apply_to_variant(variant),
+            None => variant.to_owned(),
+        };
+        m.attr
+            .add_attr(RustVariantAttr::Serde(SerdeVariantAttr::Rename(wire)));
+    }
+    let new = ident::type_name(new.to_owned());
+    match &mut m.kind {
+        RustEnumMemberKind::Nullary(v) => *v = new,
+        RustEnumMemberKind::Unary(t) => {
+            m.kind = RustEnumMemberKind::UnaryNamed {
+                variant_name: new,
+                type_name: t.clone(),
+            }
+        }
+        RustEnumMemberKind::UnaryNamed { variant_name, .. }
+        | RustEnumMemberKind::Discriminant { variant_name, .. } => *variant_name = new,
+    }
+    true
+}
+
+/// rename segments and references to them.
+/// variants named after a renamed type keep their name.
+fn rename_types(segments: &mut [RustSegment], renames: &BTreeMap<String, String>) {
+    if renames.is_empty() {
+        return;
+    }
+    for segment in segments.iter_mut() {
+        if let RustSegment::Enum(e) = segment {
+            for m in &mut e.member {
+                if let RustEnumMemberKind::Unary(RustType::Custom(TypeName { name, .. })) = &m.kind
+                {
+                    if renames.contains_key(name) {
+                        m.kind = RustEnumMemberKind::UnaryNamed {
+                            variant_name: name.to_owned(),
+                            type_name: RustType::Custom(TypeName::new(name.to_owned())),
+                        };
+                    }
+                }
+            }
+        }
+        if let Some(new) = renames.get(segment.name()) {
+            *segment.name_mut() = new.to_owned();
+        }
+    }
+
+    struct Rename<'a>(&'a BTreeMap<String, String>);
+    impl VisitMut for Rename<'_> {
+        fn visit_type_name_mut(&mut self, type_name: &mut TypeName) {
+            if let Some(new) = self.0.get(&type_name.name) {
+                type_name.name = new.to_owned();
+            }
+        }
+    }
+    visit::walk_segments_mut(&mut Rename(renames), segments);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ir::{RustFieldAttrs, RustMemberType, RustStruct};
+
+    fn member(name: &str, ty: RustType) -> RustStructMember {
+        RustStructMember {
+            attr: RustFieldAttrs::new(),
+            name: name.to_owned(),
+            ty: RustMemberType {
+                ty,
+                is_optional: false,
+                is_nullable: false,
+            },
+            comment: None,
+        }
+    }
+
+    #[test]
+    fn test_apply() {
+        let mut segments = vec![
+            RustSegment::Struct(RustStruct::from_members(
+                "Event".to_owned(),
+                None,
+                [
+                    member("private", RustType::Boolean),
+                    member(
+                        "user",
+                        RustType::Custom(TypeName::new("EventUser".to_owned())),
+                    ),
+                ]
+                .into_iter(),
+            )),
+            RustSegment::Struct(RustStruct::from_members(
+                "EventUser".to_owned(),
+                None,
+                std::iter::empty(),
+            )),
+        ];
+        let origins = Origins::from([("EventUser".to_owned(), "Event.user".to_owned())]);
+        let overrides = Overrides {
+            types: BTreeMap::from([
+                ("Event.user".to_owned(), "User".to_owned()),
+                ("Gone".to_owned(), "Renamed".to_owned()),
+            ]),
+            fields: BTreeMap::from([("Event.private".to_owned(), "is_private".to_owned())]),
+            variants: BTreeMap::from([("Event.A".to_owned(), "B".to_owned())]),
+        };
+        let unmatched = overrides.apply(&mut segments, &origins);
+        assert_eq!(
+            unmatched,
+            [
+                Unmatched {
+                    table: "variants",
+                    key: "Event.A".to_owned()
+                },
+                Unmatched {
+                    table: "types",
+                    key: "Gone".to_owned()
+                },
+            ]
+        );
+
+        let RustSegment::Struct(s) = &segments[0] else {
+            unreachable!()
+        };
+        assert_eq!(s.member[0].name, "is_private");
+        assert_eq!(s.member[0].serde_name(), "private");
+        assert_eq!(s.member[1].ty.ty.to_ident(), "User");
+        assert_eq!(segments[1].name(), "User");
+    }
+}
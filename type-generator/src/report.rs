@@ -0,0 +1,131 @@
+//! what the generated types leave to the user: fields typed as `Value` and types left out
+
+use std::fmt;
+
+use crate::{
+    ir::{RustEnum, RustSegment, RustStruct, RustType},
+    visit::{self, Visit},
+    Ir,
+};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Report {
+    /// location (`Type.field`, `Type::Variant` or `Type`) -> what is there instead of a type,
+    /// i.e. `Value` or an intersection left out. literal fields consumed as tag are not listed.
+    pub unknown: Vec<(String, &'static str)>,
+    /// type name -> why it is not generated, see [`Ir::dropped`]
+    pub dropped: Vec<(String, String)>,
+}
+
+impl Report {
+    /// report on `ir`, usually after all stages
+    pub fn new(ir: &Ir) -> Self {
+        let mut collector = Collector {
+            at: String::new(),
+            unknown: Vec::new(),
+        };
+        visit::walk_segments(&mut collector, &ir.segments);
+        Self {
+            unknown: collector.unknown,
+            dropped: ir
+                .dropped
+                .iter()
+                .map(|(name, reason)| (name.to_owned(), reason.to_owned()))
+                .collect(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.unknown.is_empty() && self.dropped.is_empty()
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "unknown types: {}", self.unknown.len())?;
+        for (at, ty) in &self.unknown {
+            writeln!(f, "  {at}: {ty}")?;
+        }
+        writeln!(f, "dropped types: {}", self.dropped.len())?;
+        for (name, reason) in &self.dropped {
+            writeln!(f, "  {name}: {reason}")?;
+        }
+        Ok(())
+    }
+}
+
+struct Collector {
+    at: String,
+    unknown: Vec<(String, &'static str)>,
+}
+
+impl Visit for Collector {
+    fn visit_segment(&mut self, segment: &RustSegment) {
+        self.at = segment.name().to_owned();
+        visit::walk_segment(self, segment);
+    }
+
+    fn visit_struct(&mut self, s: &RustStruct) {
+        for member in &s.member {
+            self.at = format!("{}.{}", s.name, member.serde_name());
+            self.visit_struct_member(member);
+        }
+    }
+
+    fn visit_enum(&mut self, e: &RustEnum) {
+        for member in &e.member {
+            self.at = format!("{}::{}", e.name, member.kind.variant_name());
+            self.visit_enum_member(member);
+        }
+    }
+
+    fn visit_type(&mut self, ty: &RustType) {
+        let unknown = match ty {
+            RustType::Unknown => "Value",
+            RustType::UnknownIntersection => "unresolved intersection, omitted",
+            RustType::UnknownLiteral => return,
+            _ => return visit::walk_type(self, ty),
+        };
+        self.unknown.push((self.at.to_owned(), unknown));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ir::{Dropped, RustFieldAttrs, RustMemberType, RustStructMember};
+
+    #[test]
+    fn test_report() {
+        let member = |name: &str, ty| RustStructMember {
+            attr: RustFieldAttrs::new(),
+            name: name.to_owned(),
+            ty: RustMemberType {
+                ty,
+                is_optional: false,
+                is_nullable: false,
+            },
+            comment: None,
+        };
+        let ir = Ir {
+            segments: vec![RustSegment::Struct(RustStruct::from_members(
+                "Event".to_owned(),
+                None,
+                [
+                    member("id", RustType::Number),
+                    member("extra", RustType::Array(Box::new(RustType::Unknown))),
+                ]
+                .into_iter(),
+            ))],
+            literal_key_map: Default::default(),
+            origins: Default::default(),
+            dropped: Dropped::from([("EventName".to_owned(), "unsupported".to_owned())]),
+        };
+        let report = Report::new(&ir);
+        assert_eq!(report.unknown, [("Event.extra".to_owned(), "Value")]);
+        assert_eq!(
+            report.to_string(),
+            "unknown types: 1\n  Event.extra: Value\ndropped types: 1\n  EventName: unsupported\n"
+        );
+    }
+}
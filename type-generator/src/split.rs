@@ -0,0 +1,242 @@
+//! split generated types into a module per event, so that unused events can be left out
+//!
+//! events are the types in the root union (`Schema`). a type belongs to the module of an event
+//! if only that event reaches it, and is shared otherwise.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+use crate::{
+    ident,
+    ir::{type_deps, type_names, RenameRule, RustSegment},
+    to_tokens, transformer,
+};
+
+pub struct EventModules {
+    /// the root, types used by several events and types not used by any event
+    pub shared: Vec<RustSegment>,
+    /// event name (e.g. `pull_request`) -> types used only by the event
+    pub events: BTreeMap<String, Vec<RustSegment>>,
+    /// traits and methods to get fields shared by events, emitted after shared types
+    pub accessors: TokenStream,
+}
+
+/// `PullRequestReviewEvent` -> `pull_request_review`
+pub fn event_name(type_name: &str) -> String {
+    let mut name = type_name
+        .strip_suffix("Event")
+        .unwrap_or(type_name)
+        .to_owned();
+    RenameRule::PascalCase.convert_to_snake(&mut name);
+    name
+}
+
+/// events in the root union, e.g. `pull_request`
+pub fn event_names(segments: &[RustSegment], root: &str) -> Vec<String> {
+    segments
+        .iter()
+        .find(|s| s.name() == root)
+        .map(type_names)
+        .unwrap_or_default()
+        .iter()
+        .map(|name| event_name(name))
+        .collect()
+}
+
+/// type name -> event owning it, i.e. the only event using it
+pub type Owners = HashMap<String, String>;
+
+/// owners of the types in `segments`.
+///
+/// computed before removing events, modules do not depend on which events are enabled.
+pub fn event_owners(segments: &[RustSegment], root: &str) -> Owners {
+    let deps = type_deps(segments);
+    let events: Vec<(String, HashSet<usize>)> = segments
+        .iter()
+        .find(|s| s.name() == root)
+        .map(type_names)
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|name| segments.iter().position(|s| s.name() == name))
+        .map(|i| (event_name(segments[i].name()), deps.reachable(i)))
+        .collect();
+    (0..segments.len())
+        .filter_map(|i| {
+            let mut users = events
+                .iter()
+                .filter(|(_, reachable)| reachable.contains(&i));
+            match (users.next(), users.next()) {
+                (Some((event, _)), None) if segments[i].name() != root => {
+                    Some((segments[i].name().to_owned(), event.to_owned()))
+                }
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// remove types of events `enabled` rejects.
+///
+/// shared types using them are removed too, and so are such variants (e.g. of the root).
+/// run before borrowing (e.g. as [`crate::Pass`]) so that lifetimes follow the remaining types.
+pub fn exclude_events(
+    segments: &mut Vec<RustSegment>,
+    owners: &Owners,
+    enabled: impl Fn(&str) -> bool,
+) {
+    let mut excluded: HashSet<String> = owners
+        .iter()
+        .filter(|(_, event)| !enabled(event))
+        .map(|(name, _)| name.to_owned())
+        .collect();
+    segments.retain(|s| !excluded.contains(s.name()));
+    loop {
+        let len = excluded.len();
+        segments.retain_mut(|segment| {
+            if let RustSegment::Enum(e) = segment {
+                e.member.retain(|m| {
+                    m.kind
+                        .as_type()
+                        .and_then(|t| t.as_custom())
+                        .map_or(true, |t| !excluded.contains(&t.name))
+                });
+            }
+            if type_names(segment).iter().any(|n| excluded.contains(n)) {
+                excluded.insert(segment.name().to_owned());
+                return false;
+            }
+            true
+        });
+        if excluded.len() == len {
+            break;
+        }
+    }
+}
+
+pub fn split_events(segments: Vec<RustSegment>, owners: &Owners) -> EventModules {
+    let mut modules = EventModules {
+        shared: Vec::new(),
+        events: BTreeMap::new(),
+        accessors: TokenStream::new(),
+    };
+    for segment in segments {
+        match owners.get(segment.name()) {
+            Some(event) => modules
+                .events
+                .entry(event.to_owned())
+                .or_default()
+                .push(segment),
+            None => modules.shared.push(segment),
+        }
+    }
+    modules
+}
+
+impl EventModules {
+    /// shared types, and a module for each event re-exported
+    pub fn into_tokens(self) -> TokenStream {
+        // `Nullable` is shared even if only used in modules
+        let mut tokens = if transformer::uses_nullable(&self.shared)
+            || self.events.values().any(|s| transformer::uses_nullable(s))
+        {
+            to_tokens::nullable_definition()
+        } else {
+            TokenStream::new()
+        };
+        tokens.extend(self.shared.into_iter().map(|s| s.into_token_stream()));
+        tokens.extend(self.accessors);
+        for (name, segments) in self.events {
+            let module = format_ident!("{}", ident::field_name(&name));
+            let segments = segments.into_iter().map(|s| s.into_token_stream());
+            tokens.extend(quote! {
+                pub mod #module {
+                    use super::*;
+                    #(#segments)*
+                }
+                pub use #module::*;
+            });
+        }
+        tokens
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ir::{
+        RustContainerAttrs, RustEnum, RustEnumMemberKind, RustFieldAttrs, RustMemberType,
+        RustStruct, RustStructMember, RustType, TypeName,
+    };
+
+    fn custom(name: &str) -> RustType {
+        RustType::Custom(TypeName::new(name.to_owned()))
+    }
+
+    fn event(name: &str, fields: &[&str]) -> RustSegment {
+        RustSegment::Struct(RustStruct::from_members(
+            name.to_owned(),
+            None,
+            fields.iter().map(|f| RustStructMember {
+                attr: RustFieldAttrs::new(),
+                name: f.to_lowercase(),
+                ty: RustMemberType {
+                    ty: custom(f),
+                    is_optional: false,
+                    is_nullable: false,
+                },
+                comment: None,
+            }),
+        ))
+    }
+
+    fn segments() -> Vec<RustSegment> {
+        vec![
+            RustSegment::Enum(RustEnum {
+                attr: RustContainerAttrs::new(),
+                name: "Schema".to_owned(),
+                comment: None,
+                is_borrowed: false,
+                member: ["PushEvent", "PingEvent"]
+                    .iter()
+                    .map(|e| RustEnumMemberKind::Unary(custom(e)).into())
+                    .collect(),
+            }),
+            event("PushEvent", &["Commit", "User"]),
+            event("PingEvent", &["Hook", "User"]),
+            event("Commit", &[]),
+            event("Hook", &[]),
+            event("User", &[]),
+        ]
+    }
+
+    fn names(segments: &[RustSegment]) -> Vec<&str> {
+        segments.iter().map(|s| s.name()).collect()
+    }
+
+    #[test]
+    fn test_split_events() {
+        let segments = segments();
+        assert_eq!(event_names(&segments, "Schema"), ["push", "ping"]);
+        let owners = event_owners(&segments, "Schema");
+        let modules = split_events(segments, &owners);
+        assert_eq!(names(&modules.shared), ["Schema", "User"]);
+        assert_eq!(names(&modules.events["push"]), ["PushEvent", "Commit"]);
+        assert_eq!(names(&modules.events["ping"]), ["PingEvent", "Hook"]);
+
+        assert!(modules.into_tokens().to_string().contains("pub mod push"));
+    }
+
+    #[test]
+    fn test_exclude_events() {
+        let mut excluded = segments();
+        let owners = event_owners(&excluded, "Schema");
+        exclude_events(&mut excluded, &owners, |e| e == "push");
+        assert_eq!(names(&excluded), ["Schema", "PushEvent", "Commit", "User"]);
+        let RustSegment::Enum(schema) = &excluded[0] else {
+            unreachable!()
+        };
+        assert_eq!(schema.member.len(), 1);
+    }
+}
@@ -2,16 +2,23 @@ use proc_macro2::{TokenStream, TokenTree};
 use quote::{quote, ToTokens, TokenStreamExt};
 
 use crate::ir::{
-    Attrs, RustAlias, RustComment, RustEnum, RustEnumMember, RustEnumMemberKind, RustFieldAttr,
-    RustMemberType, RustSegment, RustStruct, RustStructAttr, RustStructMember, RustType,
-    RustVariantAttr, SerdeContainerAttr, SerdeFieldAttr, SerdeVariantAttr, TypeName,
+    Attrs, LiteralValue, RustAlias, RustComment, RustConst, RustEnum, RustEnumMember,
+    RustEnumMemberKind, RustFieldAttr, RustMemberType, RustSegment, RustStruct, RustStructAttr,
+    RustStructMember, RustType, RustVariantAttr, SerdeContainerAttr, SerdeFieldAttr,
+    SerdeVariantAttr, TypeName,
 };
 
+/// identifier, `r#` prefixed one as raw identifier
 macro_rules! id {
-    ($($tt:tt)*) => {
-        proc_macro2::Ident::new($($tt)*, proc_macro2::Span::call_site())
-    };
+    ($($tt:tt)*) => {{
+        let s: &str = $($tt)*;
+        match s.strip_prefix("r#") {
+            Some(raw) => proc_macro2::Ident::new_raw(raw, proc_macro2::Span::call_site()),
+            None => proc_macro2::Ident::new(s, proc_macro2::Span::call_site()),
+        }
+    }};
 }
+pub(crate) use id;
 
 impl RustSegment {
     pub fn into_token_stream(self) -> TokenStream {
@@ -19,6 +26,7 @@ impl RustSegment {
             RustSegment::Struct(s) => s.into_token_stream(),
             RustSegment::Enum(e) => e.into_token_stream(),
             RustSegment::Alias(a) => a.into_token_stream(),
+            RustSegment::Const(c) => c.into_token_stream(),
         }
     }
 }
@@ -32,74 +40,76 @@ impl ToTokens for TypeName {
         } else {
             quote!()
         };
-        tokens.extend(
-            quote! {
-                #name #p
-            },
-        )
+        tokens.extend(quote! {
+            #name #p
+        })
     }
 }
 
 impl ToTokens for RustStructAttr {
     fn to_tokens(&self, tokens: &mut TokenStream) {
-        tokens.extend(
-            match self {
-                RustStructAttr::Serde(s) => quote! {
-                    #[serde(#s)]
-                },
+        tokens.extend(match self {
+            RustStructAttr::Serde(s) => quote! {
+                #[serde(#s)]
             },
-        )
+            RustStructAttr::Derive(path) => {
+                let path: TokenStream = path.parse().expect("path of derive");
+                quote! {
+                    #[derive(#path)]
+                }
+            }
+        })
     }
 }
 
 impl ToTokens for SerdeContainerAttr {
     fn to_tokens(&self, tokens: &mut TokenStream) {
-        tokens.extend(
-            match self {
-                SerdeContainerAttr::RenameAll(r) => {
-                    let r = r.to_string();
-                    quote! {
-                        rename_all = #r
-                    }
+        tokens.extend(match self {
+            SerdeContainerAttr::RenameAll(r) => {
+                let r = r.to_string();
+                quote! {
+                    rename_all = #r
                 }
-                SerdeContainerAttr::Tag(name) => quote! {
-                    tag = #name
-                },
-                SerdeContainerAttr::Untagged => quote! {
-                    untagged
-                },
+            }
+            SerdeContainerAttr::Tag(name) => quote! {
+                tag = #name
+            },
+            SerdeContainerAttr::Untagged => quote! {
+                untagged
             },
-        )
+        })
     }
 }
 
 impl ToTokens for RustFieldAttr {
     fn to_tokens(&self, tokens: &mut TokenStream) {
-        tokens.extend(
-            match self {
-                RustFieldAttr::Serde(s) => quote! {
-                    #[serde(#s)]
-                },
+        tokens.extend(match self {
+            RustFieldAttr::Serde(s) => quote! {
+                #[serde(#s)]
             },
-        )
+        })
     }
 }
 
 impl ToTokens for SerdeFieldAttr {
     fn to_tokens(&self, tokens: &mut TokenStream) {
-        tokens.extend(
-            match self {
-                SerdeFieldAttr::Rename(s) => quote! {
-                    rename = #s
-                },
-                SerdeFieldAttr::Borrow => quote! {
-                    borrow = "'a"
-                },
-                SerdeFieldAttr::Flatten => quote! {
-                    flatten
-                },
+        tokens.extend(match self {
+            SerdeFieldAttr::Rename(s) => quote! {
+                rename = #s
+            },
+            SerdeFieldAttr::Borrow => quote! {
+                borrow = "'a"
             },
-        )
+            SerdeFieldAttr::Flatten => quote! {
+                flatten
+            },
+            SerdeFieldAttr::Default => quote! {
+                default
+            },
+            SerdeFieldAttr::SkipSerializingIf(p) => quote! {
+                skip_serializing_if = #p
+            },
+        })
     }
 }
 
@@ -108,16 +118,15 @@ impl ToTokens for RustType {
         let s = match self {
             RustType::String { is_borrowed } => {
                 if *is_borrowed {
-                    tokens.extend(
-                        quote! {
-                            &'a str
-                        },
-                    );
+                    tokens.extend(quote! {
+                        &'a str
+                    });
                     return;
                 }
                 "String"
             }
             RustType::Number => "usize",
+            RustType::Float => "f64",
             RustType::Boolean => "bool",
             RustType::Custom(TypeName { name, is_borrowed }) => {
                 let name = id!(name);
@@ -126,19 +135,21 @@ impl ToTokens for RustType {
                 } else {
                     quote!()
                 };
-                tokens.extend(
-                    quote! {
-                        #name #p
-                    },
-                );
+                tokens.extend(quote! {
+                    #name #p
+                });
                 return;
             }
             RustType::Array(t) => {
-                tokens.extend(
-                    quote! {
-                        Vec<#t>
-                    },
-                );
+                tokens.extend(quote! {
+                    Vec<#t>
+                });
+                return;
+            }
+            RustType::Nullable(t) => {
+                tokens.extend(quote! {
+                    Nullable<#t>
+                });
                 return;
             }
             RustType::Unit => {
@@ -152,12 +163,17 @@ impl ToTokens for RustType {
             RustType::Unknown => "Value",
             RustType::UnknownLiteral => "UnknownLiteral",
             RustType::UnknownIntersection => "UnknownIntersection",
+            RustType::Literal(l) => unreachable!("literal {l:?} should be lowered by transformer"),
             RustType::Map(t1, t2) => {
-                tokens.extend(
-                    quote! {
-                        HashMap<#t1, #t2>
-                    },
-                );
+                tokens.extend(quote! {
+                    HashMap<#t1, #t2>
+                });
+                return;
+            }
+            RustType::Tuple(ts) => {
+                tokens.extend(quote! {
+                    (#(#ts,)*)
+                });
                 return;
             }
         };
@@ -165,20 +181,64 @@ impl ToTokens for RustType {
     }
 }
 
+/// definition of [`RustType::Nullable`], emitted once if used
+pub fn nullable_definition() -> TokenStream {
+    quote! {
+        #[doc = "field which may be absent, `null` or a value"]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub enum Nullable<T> {
+            Absent,
+            Null,
+            Value(T),
+        }
+        impl<T> Default for Nullable<T> {
+            fn default() -> Self {
+                Nullable::Absent
+            }
+        }
+        impl<T> Nullable<T> {
+            pub fn as_option(&self) -> Option<Option<&T>> {
+                match self {
+                    Nullable::Absent => None,
+                    Nullable::Null => Some(None),
+                    Nullable::Value(v) => Some(Some(v)),
+                }
+            }
+            pub fn is_absent(&self) -> bool {
+                matches!(self, Nullable::Absent)
+            }
+        }
+        impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for Nullable<T> {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                Ok(match <Option<T> as serde::Deserialize>::deserialize(deserializer)? {
+                    Some(v) => Nullable::Value(v),
+                    None => Nullable::Null,
+                })
+            }
+        }
+        impl<T: serde::Serialize> serde::Serialize for Nullable<T> {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                match self {
+                    Nullable::Absent | Nullable::Null => serializer.serialize_none(),
+                    Nullable::Value(v) => serializer.serialize_some(v),
+                }
+            }
+        }
+    }
+}
+
 impl ToTokens for RustMemberType {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let inner_ty = &self.ty;
-        tokens.extend(
-            if self.is_optional {
-                quote! {
-                    Option<#inner_ty>
-                }
-            } else {
-                quote! {
-                    #inner_ty
-                }
-            },
-        )
+        tokens.extend(if self.is_optional || self.is_nullable {
+            quote! {
+                Option<#inner_ty>
+            }
+        } else {
+            quote! {
+                #inner_ty
+            }
+        })
     }
 }
 
@@ -193,13 +253,11 @@ impl ToTokens for RustStructMember {
         let name = id!(name);
 
         if !self.ty.is_unknown() {
-            tokens.extend(
-                quote! {
-                    #comment
-                    #attr
-                    pub #name: #ty,
-                },
-            );
+            tokens.extend(quote! {
+                #comment
+                #attr
+                pub #name: #ty,
+            });
         }
     }
 }
@@ -208,11 +266,9 @@ impl ToTokens for RustComment {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let c = &self.0;
 
-        tokens.extend(
-            quote! {
-                #[doc=#c]
-            },
-        );
+        tokens.extend(quote! {
+            #[doc=#c]
+        });
     }
 }
 
@@ -240,13 +296,11 @@ impl ToTokens for RustStruct {
             quote!()
         };
 
-        tokens.extend(
-            quote! {
-                pub struct #name #p {
-                    #(#member)*
-                }
-            },
-        );
+        tokens.extend(quote! {
+            pub struct #name #p {
+                #(#member)*
+            }
+        });
     }
 }
 
@@ -259,6 +313,10 @@ impl ToTokens for RustEnum {
             attr,
             is_borrowed,
         } = self;
+        if member.iter().any(|m| m.kind.is_discriminant()) {
+            discriminant_enum(self, tokens);
+            return;
+        }
         let name = id!(name);
         tokens.extend(
             if attr.as_inner().iter().any(|a| a.as_serde().is_some())
@@ -279,15 +337,13 @@ impl ToTokens for RustEnum {
         } else {
             quote!()
         };
-        tokens.extend(
-            quote! {
-                #attr
-                #comment
-                pub enum #name #p {
-                    #(#member)*
-                }
-            },
-        );
+        tokens.extend(quote! {
+            #attr
+            #comment
+            pub enum #name #p {
+                #(#member)*
+            }
+        });
     }
 }
 
@@ -305,49 +361,201 @@ impl ToTokens for RustAlias {
         } else {
             quote!()
         };
-        tokens.extend(
-            quote! {
-                #comment
-                pub type #ident #p = #typ;
-            },
-        )
+        tokens.extend(quote! {
+            #comment
+            pub type #ident #p = #typ;
+        })
     }
 }
 
-impl ToTokens for RustEnumMemberKind {
+impl ToTokens for RustConst {
     fn to_tokens(&self, tokens: &mut TokenStream) {
-        tokens.extend(
-            match self {
-                RustEnumMemberKind::Nullary(v) => {
-                    let v = id!(v);
-                    quote!(#v,)
-                }
-                RustEnumMemberKind::Unary(a) => {
-                    if a.is_unknown() {
-                        quote! {
-                            Other,
-                        }
+        let Self {
+            name,
+            value,
+            comment,
+        } = self;
+        let ident = id!(name);
+        // `expected` of errors, `&str` or a string literal of the value as others are not
+        // `serde::de::Expected`
+        let (ty, de_ty, unexpected, expected, serialize) = match value {
+            LiteralValue::String(_) => (
+                quote!(&'static str),
+                quote!(std::borrow::Cow<'de, str>),
+                quote!(Str(&value)),
+                quote!(&Self::VALUE),
+                quote!(serialize_str),
+            ),
+            LiteralValue::Number(n) => (
+                quote!(i64),
+                quote!(i64),
+                quote!(Signed(value)),
+                {
+                    let n = n.to_string();
+                    quote!(&#n)
+                },
+                quote!(serialize_i64),
+            ),
+            LiteralValue::Boolean(b) => (
+                quote!(bool),
+                quote!(bool),
+                quote!(Bool(value)),
+                {
+                    let b = b.to_string();
+                    quote!(&#b)
+                },
+                quote!(serialize_bool),
+            ),
+        };
+        tokens.extend(quote! {
+            #comment
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+            pub struct #ident;
+            impl #ident {
+                pub const VALUE: #ty = #value;
+            }
+            impl<'de> serde::Deserialize<'de> for #ident {
+                fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    let value = <#de_ty as serde::Deserialize>::deserialize(deserializer)?;
+                    if value == Self::VALUE {
+                        Ok(Self)
                     } else {
-                        let n = a.to_ident();
-                        let n = id!(n);
-                        quote!(#n(#a),)
+                        Err(serde::de::Error::invalid_value(
+                            serde::de::Unexpected::#unexpected,
+                            #expected,
+                        ))
                     }
                 }
-                RustEnumMemberKind::UnaryNamed {
-                    variant_name,
-                    type_name,
-                } => {
-                    if type_name.is_unknown() {
-                        quote! {
-                            Other,
-                        }
-                    } else {
-                        let variant_name = id!(variant_name);
-                        quote!(#variant_name(#type_name),)
+            }
+            impl serde::Serialize for #ident {
+                fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    serializer.#serialize(Self::VALUE)
+                }
+            }
+        })
+    }
+}
+
+impl ToTokens for LiteralValue {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        match self {
+            LiteralValue::String(s) => s.to_tokens(tokens),
+            LiteralValue::Number(n) => tokens.append(proc_macro2::Literal::i64_unsuffixed(*n)),
+            LiteralValue::Boolean(b) => b.to_tokens(tokens),
+        }
+    }
+}
+
+/// numeric literal union, (de)serialized as `i64` like `serde_repr`
+fn discriminant_enum(e: &RustEnum, tokens: &mut TokenStream) {
+    let RustEnum {
+        name,
+        member,
+        comment,
+        ..
+    } = e;
+    let ident = id!(name);
+    let (names, values): (Vec<_>, Vec<_>) = member
+        .iter()
+        .map(|m| match &m.kind {
+            RustEnumMemberKind::Discriminant {
+                variant_name,
+                value,
+            } => (
+                id!(variant_name),
+                proc_macro2::Literal::i64_unsuffixed(*value),
+            ),
+            _ => unreachable!("mixed numeric literal union"),
+        })
+        .unzip();
+    let range = member.iter().flat_map(|m| match m.kind {
+        RustEnumMemberKind::Discriminant { value, .. } => Some(value),
+        _ => None,
+    });
+    let (lo, hi) = range.fold((0, 0), |(lo, hi), v| (v.min(lo), v.max(hi)));
+    let repr = id!(match (lo, hi) {
+        (0.., ..=0xff) => "u8",
+        (0.., ..=0xffff) => "u16",
+        (0.., ..=0xffff_ffff) => "u32",
+        (-0x80.., ..=0x7f) => "i8",
+        (-0x8000.., ..=0x7fff) => "i16",
+        (-0x8000_0000.., ..=0x7fff_ffff) => "i32",
+        _ => "i64",
+    });
+    let expected = format!(
+        "one of {}",
+        values
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    tokens.extend(quote! {
+        #comment
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        #[repr(#repr)]
+        pub enum #ident {
+            #(#names = #values,)*
+        }
+        impl<'de> serde::Deserialize<'de> for #ident {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                match <i64 as serde::Deserialize>::deserialize(deserializer)? {
+                    #(#values => Ok(Self::#names),)*
+                    value => Err(serde::de::Error::invalid_value(
+                        serde::de::Unexpected::Signed(value),
+                        &#expected,
+                    )),
+                }
+            }
+        }
+        impl serde::Serialize for #ident {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_i64(*self as i64)
+            }
+        }
+    });
+}
+
+impl ToTokens for RustEnumMemberKind {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        tokens.extend(match self {
+            RustEnumMemberKind::Nullary(v) => {
+                let v = id!(v);
+                quote!(#v,)
+            }
+            RustEnumMemberKind::Unary(a) => {
+                if a.is_unknown() {
+                    quote! {
+                        Other,
                     }
+                } else {
+                    let n = a.to_ident();
+                    let n = id!(n);
+                    quote!(#n(#a),)
                 }
-            },
-        )
+            }
+            RustEnumMemberKind::Discriminant {
+                variant_name,
+                value,
+            } => {
+                let variant_name = id!(variant_name);
+                let value = proc_macro2::Literal::i64_unsuffixed(*value);
+                quote!(#variant_name = #value,)
+            }
+            RustEnumMemberKind::UnaryNamed {
+                variant_name,
+                type_name,
+            } => {
+                if type_name.is_unknown() {
+                    quote! {
+                        Other,
+                    }
+                } else {
+                    let variant_name = id!(variant_name);
+                    quote!(#variant_name(#type_name),)
+                }
+            }
+        })
     }
 }
 
@@ -355,11 +563,9 @@ impl<Field: ToTokens> ToTokens for Attrs<Field> {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         if !self.as_inner().is_empty() {
             let ws = self.as_inner();
-            tokens.extend(
-                quote! {
-                    #(#ws)*
-                },
-            )
+            tokens.extend(quote! {
+                #(#ws)*
+            })
         }
     }
 }
@@ -367,38 +573,32 @@ impl<Field: ToTokens> ToTokens for Attrs<Field> {
 impl ToTokens for RustEnumMember {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let Self { attr, kind } = self;
-        tokens.extend(
-            quote! {
-                #attr
-                #kind
-            },
-        )
+        tokens.extend(quote! {
+            #attr
+            #kind
+        })
     }
 }
 
 impl ToTokens for RustVariantAttr {
     fn to_tokens(&self, tokens: &mut TokenStream) {
-        tokens.extend(
-            match self {
-                RustVariantAttr::Serde(s) => quote! {
-                    #[serde(#s)]
-                },
+        tokens.extend(match self {
+            RustVariantAttr::Serde(s) => quote! {
+                #[serde(#s)]
             },
-        )
+        })
     }
 }
 
 impl ToTokens for SerdeVariantAttr {
     fn to_tokens(&self, tokens: &mut TokenStream) {
-        tokens.extend(
-            match self {
-                SerdeVariantAttr::Rename(s) => quote! {
-                    rename = #s
-                },
-                SerdeVariantAttr::Borrow => quote! {
-                    borrow = "'a"
-                },
+        tokens.extend(match self {
+            SerdeVariantAttr::Rename(s) => quote! {
+                rename = #s
+            },
+            SerdeVariantAttr::Borrow => quote! {
+                borrow = "'a"
             },
-        )
+        })
     }
 }
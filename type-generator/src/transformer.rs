@@ -1,10 +1,20 @@
 mod borrow;
+mod dedup;
+mod derive;
 mod flatten_type;
 mod internal_tag;
+mod literal_const;
+mod merge_intersection;
 mod rename_all;
 mod retype;
+mod tri_state;
 
 pub use borrow::adapt_borrow;
+pub use dedup::dedup_types;
+pub use derive::add_derives;
 pub use flatten_type::flatten_type;
 pub use internal_tag::adapt_internal_tag;
+pub use literal_const::adapt_literal_const;
+pub use merge_intersection::merge_intersection;
 pub use rename_all::adapt_rename_all;
+pub use tri_state::{adapt_tri_state, uses_nullable};
@@ -6,8 +6,30 @@ use crate::{
         RustFieldAttr, RustSegment, RustType, RustVariantAttr, SerdeFieldAttr, SerdeVariantAttr,
         TypeName,
     },
+    visit::{self, VisitMut},
 };
 
+struct Borrow<'a> {
+    did_borrow: bool,
+    decorated: &'a HashSet<String>,
+}
+
+impl VisitMut for Borrow<'_> {
+    fn visit_type_mut(&mut self, ty: &mut RustType) {
+        if let RustType::String { is_borrowed } = ty {
+            *is_borrowed = true;
+            self.did_borrow = true;
+        }
+        visit::walk_type_mut(self, ty);
+    }
+    fn visit_type_name_mut(&mut self, TypeName { name, is_borrowed }: &mut TypeName) {
+        if self.decorated.contains(name) {
+            *is_borrowed = true;
+            self.did_borrow = true;
+        }
+    }
+}
+
 pub fn adapt_borrow(segments: &mut [RustSegment], type_deps: &CoDirectedAcyclicGraph<usize>) {
     let mut decorated: HashSet<String> = HashSet::new();
     let sorted = match type_deps.co_topo_sort() {
@@ -23,47 +45,18 @@ pub fn adapt_borrow(segments: &mut [RustSegment], type_deps: &CoDirectedAcyclicG
     };
     for index in sorted {
         let seg = segments.get_mut(index).unwrap();
-        fn borrow_typename(
-            TypeName { name, is_borrowed }: &mut TypeName,
-            did_borrow: &mut bool,
-            decorated: &HashSet<String>,
-        ) {
-            if decorated.contains(name) {
-                *is_borrowed = true;
-                *did_borrow = true;
-            }
-        }
-        fn borrow_type(ty: &mut RustType, did_borrow: &mut bool, decorated: &HashSet<String>) {
-            match ty {
-                RustType::String { is_borrowed } => {
-                    *is_borrowed = true;
-                    *did_borrow = true;
-                }
-                RustType::Number => (),
-                RustType::Boolean => (),
-                RustType::Custom(t) => {
-                    borrow_typename(t, did_borrow, decorated);
-                }
-                RustType::Array(t) => borrow_type(t, did_borrow, decorated),
-                RustType::Unit => (),
-                RustType::Unknown => (),
-                RustType::UnknownLiteral => (),
-                RustType::UnknownIntersection => (),
-                RustType::Map(t1, t2) => {
-                    borrow_type(t1, did_borrow, decorated);
-                    borrow_type(t2, did_borrow, decorated);
-                }
-            }
-        }
-        let mut did_borrow = false;
+        let mut b = Borrow {
+            did_borrow: false,
+            decorated: &decorated,
+        };
         match seg {
             RustSegment::Struct(s) => {
                 let mut visible = false;
                 for mem in &mut s.member {
-                    borrow_type(&mut mem.ty.ty, &mut did_borrow, &decorated);
+                    b.visit_type_mut(&mut mem.ty.ty);
                     visible |= mem.ty.ty.is_string();
                 }
-                if did_borrow {
+                if b.did_borrow {
                     if !visible {
                         for mem in &mut s.member {
                             if mem.ty.ty.is_borrowed() {
@@ -81,11 +74,11 @@ pub fn adapt_borrow(segments: &mut [RustSegment], type_deps: &CoDirectedAcyclicG
                 let mut visible = false;
                 for mem in &mut e.member {
                     if let Some(t) = mem.kind.as_type_mut() {
-                        borrow_type(t, &mut did_borrow, &decorated);
+                        b.visit_type_mut(t);
                         visible |= t.is_string();
                     }
                 }
-                if did_borrow {
+                if b.did_borrow {
                     if !visible {
                         for mem in &mut e.member {
                             if let Some(t) = mem.kind.as_type() {
@@ -102,13 +95,13 @@ pub fn adapt_borrow(segments: &mut [RustSegment], type_deps: &CoDirectedAcyclicG
                 }
             }
             RustSegment::Alias(a) => {
-                let ty = &mut a.ty;
-                borrow_type(ty, &mut did_borrow, &decorated);
-                if did_borrow {
+                b.visit_type_mut(&mut a.ty);
+                if b.did_borrow {
                     a.is_borrowed = true;
                     decorated.insert(a.name.to_owned());
                 }
             }
+            RustSegment::Const(_) => (),
         }
     }
 }
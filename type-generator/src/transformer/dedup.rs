@@ -0,0 +1,98 @@
+use std::collections::{HashMap, HashSet};
+
+use quote::ToTokens;
+
+use crate::ir::{Origins, RustEnumMemberKind, RustSegment, RustType, TypeName};
+
+use super::retype;
+
+/// merges structs and enums of the same structure, ignoring names and comments.
+///
+/// only types named after their path (i.e. in `origins`) are merged away; each group of
+/// identical types is represented by a declared type if any, or else by the first one.
+/// merging may make the referring types identical, so this repeats until nothing changes.
+pub fn dedup_types(segments: &mut Vec<RustSegment>, origins: &Origins) {
+    loop {
+        // structure -> canonical name
+        let mut canonical: HashMap<String, &str> = HashMap::new();
+        let keys: Vec<Option<String>> = segments.iter_mut().map(structure).collect();
+        for (segment, key) in segments.iter().zip(&keys) {
+            let Some(key) = key else {
+                continue;
+            };
+            let name = segment.name();
+            canonical
+                .entry(key.to_owned())
+                .and_modify(|c| {
+                    if origins.contains_key(*c) && !origins.contains_key(name) {
+                        *c = name;
+                    }
+                })
+                .or_insert(name);
+        }
+
+        let mut retype_map: HashMap<String, RustType> = HashMap::new();
+        for (segment, key) in segments.iter().zip(&keys) {
+            let Some(key) = key else {
+                continue;
+            };
+            let name = segment.name();
+            let c = canonical[key];
+            if c != name && origins.contains_key(name) {
+                retype_map.insert(
+                    name.to_owned(),
+                    RustType::Custom(TypeName::new(c.to_owned())),
+                );
+            }
+        }
+        if retype_map.is_empty() {
+            return;
+        }
+
+        let merged: HashSet<String> = retype_map.keys().cloned().collect();
+        segments.retain(|s| !merged.contains(s.name()));
+        // variants named after merged types keep their name
+        for segment in segments.iter_mut() {
+            let RustSegment::Enum(e) = segment else {
+                continue;
+            };
+            for m in &mut e.member {
+                if let RustEnumMemberKind::Unary(RustType::Custom(tn)) = &m.kind {
+                    if merged.contains(&tn.name) {
+                        let variant_name = tn.name.to_owned();
+                        m.kind.name_unary(variant_name);
+                    }
+                }
+            }
+        }
+        retype::retype(segments, retype_map);
+    }
+}
+
+/// tokens of the struct or enum without its name and comments
+fn structure(segment: &mut RustSegment) -> Option<String> {
+    const PLACEHOLDER: &str = "Dedup";
+    let (name, key) = match segment {
+        RustSegment::Struct(s) => {
+            let name = std::mem::replace(&mut s.name, PLACEHOLDER.to_owned());
+            let comment = s.comment.take();
+            let member_comments: Vec<_> = s.member.iter_mut().map(|m| m.comment.take()).collect();
+            let key = s.to_token_stream().to_string();
+            s.comment = comment;
+            for (m, c) in s.member.iter_mut().zip(member_comments) {
+                m.comment = c;
+            }
+            (name, key)
+        }
+        RustSegment::Enum(e) => {
+            let name = std::mem::replace(&mut e.name, PLACEHOLDER.to_owned());
+            let comment = e.comment.take();
+            let key = e.to_token_stream().to_string();
+            e.comment = comment;
+            (name, key)
+        }
+        RustSegment::Alias(_) | RustSegment::Const(_) => return None,
+    };
+    *segment.name_mut() = name;
+    Some(key)
+}
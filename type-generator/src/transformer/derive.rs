@@ -0,0 +1,19 @@
+use crate::ir::{RustSegment, RustStructAttr};
+
+/// derives `derives` for structs and enums, in addition to the built-in derives.
+///
+/// numeric enums and literal constants already derive the common traits, and are left as is.
+pub fn add_derives(segments: &mut [RustSegment], derives: &[String]) {
+    for segment in segments {
+        let attr = match segment {
+            RustSegment::Struct(s) => &mut s.attr,
+            RustSegment::Enum(e) if !e.member.iter().any(|m| m.kind.is_discriminant()) => {
+                &mut e.attr
+            }
+            _ => continue,
+        };
+        for d in derives {
+            attr.add_attr(RustStructAttr::Derive(d.to_owned()));
+        }
+    }
+}
@@ -1,29 +1,39 @@
 use std::collections::HashMap;
 
-use crate::ir::{RustFieldAttr, RustMemberType, RustSegment, RustType, SerdeFieldAttr};
+use crate::{
+    ir::{RustFieldAttr, RustMemberType, RustSegment, RustStruct, RustType, SerdeFieldAttr},
+    visit::{self, Visit},
+};
 
 use super::retype;
 
-/// flattens type with only one field attributed `#[serde(flatten)]`
+/// flattens type with only one (emitted) field attributed `#[serde(flatten)]`
 pub fn flatten_type(segments: &mut Vec<RustSegment>) {
-    let mut retype_map: HashMap<String, RustType> = HashMap::new();
-    segments.retain_mut(|segment| match segment {
-        RustSegment::Struct(s) => {
-            if s.member.len() == 1 {
-                let r = s.member.first().unwrap();
-                if r.attr
-                    .as_inner()
-                    .contains(&RustFieldAttr::Serde(SerdeFieldAttr::Flatten))
-                {
-                    let RustMemberType { ty, is_optional } = &r.ty;
-                    assert!(!is_optional);
-                    retype_map.insert(s.name.to_owned(), ty.to_owned());
-                    return false;
-                }
+    struct Flatten(HashMap<String, RustType>);
+    impl Visit for Flatten {
+        fn visit_struct(&mut self, s: &RustStruct) {
+            // unknown members (e.g. literals consumed as tag) are not emitted
+            let mut member = s.member.iter().filter(|m| !m.ty.is_unknown());
+            let (Some(r), None) = (member.next(), member.next()) else {
+                return;
+            };
+            if r.attr
+                .as_inner()
+                .contains(&RustFieldAttr::Serde(SerdeFieldAttr::Flatten))
+            {
+                let RustMemberType {
+                    ty,
+                    is_optional,
+                    is_nullable,
+                } = &r.ty;
+                assert!(!is_optional && !is_nullable);
+                self.0.insert(s.name.to_owned(), ty.to_owned());
             }
-            true
         }
-        _ => true,
-    });
+    }
+    let mut flatten = Flatten(HashMap::new());
+    visit::walk_segments(&mut flatten, segments);
+    let retype_map = flatten.0;
+    segments.retain(|s| !matches!(s, RustSegment::Struct(s) if retype_map.contains_key(&s.name)));
     retype::retype(segments, retype_map)
 }
@@ -0,0 +1,141 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    case, ident,
+    ir::{
+        LiteralValue, RustConst, RustFieldAttr, RustSegment, RustStructAttr, RustType,
+        SerdeContainerAttr, SerdeFieldAttr, TypeName,
+    },
+};
+
+/// lowers literal fields into [`RustConst`] marker types.
+///
+/// fields consumed as tag of internally tagged enums are not emitted, because serde passes
+/// the content without the tag to variants. neither are fields whose key a flattened field
+/// also reads, as the marker would take the key away from it.
+///
+/// fields of the same key and value share the constant, e.g. ones copied by `Partial<T>`.
+pub fn adapt_literal_const(segments: &mut Vec<RustSegment>) {
+    // type name -> tag field name
+    let mut tags: HashMap<String, String> = HashMap::new();
+    for segment in segments.iter() {
+        let RustSegment::Enum(e) = segment else {
+            continue;
+        };
+        for a in e.attr.as_inner() {
+            let RustStructAttr::Serde(SerdeContainerAttr::Tag(tag)) = a else {
+                continue;
+            };
+            for m in &e.member {
+                if let Some(RustType::Custom(tn)) = m.kind.as_type() {
+                    tags.insert(tn.name.to_owned(), tag.to_owned());
+                }
+            }
+        }
+    }
+
+    // struct name -> keys read by its flattened fields
+    let shadowed: HashMap<String, HashSet<String>> = segments
+        .iter()
+        .filter_map(|s| match s {
+            RustSegment::Struct(s) => Some((s.name.to_owned(), flattened_keys(segments, s))),
+            _ => None,
+        })
+        .collect();
+
+    let mut names: HashSet<String> = segments.iter().map(|s| s.name().to_owned()).collect();
+    // (key, value) -> constant
+    let mut consts: HashMap<(String, LiteralValue), String> = HashMap::new();
+    // place constants just before the struct using them
+    let mut lowered = Vec::with_capacity(segments.len());
+    for mut segment in std::mem::take(segments) {
+        let RustSegment::Struct(s) = &mut segment else {
+            lowered.push(segment);
+            continue;
+        };
+        let tag = tags.get(&s.name);
+        for m in &mut s.member {
+            let RustType::Literal(value) = &m.ty.ty else {
+                continue;
+            };
+            if tag.map(String::as_str) == Some(m.serde_name())
+                || shadowed[&s.name].contains(m.serde_name())
+            {
+                m.ty.ty = RustType::UnknownLiteral;
+                continue;
+            }
+            let key = (m.serde_name().to_owned(), value.to_owned());
+            if let Some(name) = consts.get(&key) {
+                m.ty.ty = RustType::Custom(TypeName::new(name.to_owned()));
+                continue;
+            }
+            let mut field = ident::unraw(&m.name).trim_end_matches('_').to_owned();
+            case::detect_case(&field)
+                .into_rename_rule()
+                .convert_to_pascal(&mut field);
+            let base = format!("{}{}", s.name, field);
+            let mut name = base.clone();
+            let mut n = 1;
+            while names.contains(&name) {
+                n += 1;
+                name = format!("{base}{n}");
+            }
+            names.insert(name.clone());
+            consts.insert(key, name.clone());
+            lowered.push(RustSegment::Const(RustConst {
+                name: name.clone(),
+                value: value.to_owned(),
+                comment: None,
+            }));
+            m.ty.ty = RustType::Custom(TypeName::new(name));
+        }
+        lowered.push(segment);
+    }
+    *segments = lowered;
+}
+
+fn is_flatten(attr: &RustFieldAttr) -> bool {
+    matches!(attr, RustFieldAttr::Serde(SerdeFieldAttr::Flatten))
+}
+
+/// keys the flattened fields of `s` read, through nested flattened fields and variants
+fn flattened_keys(segments: &[RustSegment], s: &crate::ir::RustStruct) -> HashSet<String> {
+    let mut keys = HashSet::new();
+    let mut visited = HashSet::new();
+    let mut queue: Vec<&str> = s
+        .member
+        .iter()
+        .filter(|m| m.attr.as_inner().iter().any(is_flatten))
+        .filter_map(|m| Some(m.ty.ty.as_custom()?.name.as_str()))
+        .collect();
+    while let Some(name) = queue.pop() {
+        if !visited.insert(name) {
+            continue;
+        }
+        match segments.iter().find(|s| s.name() == name) {
+            Some(RustSegment::Struct(t)) => {
+                for m in &t.member {
+                    if m.attr.as_inner().iter().any(is_flatten) {
+                        queue.extend(m.ty.ty.as_custom().map(|t| t.name.as_str()));
+                    } else {
+                        keys.insert(m.serde_name().to_owned());
+                    }
+                }
+            }
+            Some(RustSegment::Enum(e)) => {
+                for a in e.attr.as_inner() {
+                    if let RustStructAttr::Serde(SerdeContainerAttr::Tag(tag)) = a {
+                        keys.insert(tag.to_owned());
+                    }
+                }
+                for m in &e.member {
+                    if let Some(RustType::Custom(tn)) = m.kind.as_type() {
+                        queue.push(&tn.name);
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+    keys
+}
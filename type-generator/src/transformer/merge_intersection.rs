@@ -0,0 +1,61 @@
+use std::collections::HashMap;
+
+use crate::ir::{RustFieldAttr, RustSegment, RustStructMember, RustType, SerdeFieldAttr};
+
+fn is_flatten(m: &RustStructMember) -> bool {
+    m.attr
+        .as_inner()
+        .contains(&RustFieldAttr::Serde(SerdeFieldAttr::Flatten))
+}
+
+/// merges members of plain structs into a struct consisting only of fields attributed
+/// `#[serde(flatten)]` with them (e.g. `A & B` of interfaces)
+///
+/// a struct is plain if it has no container attribute and no flatten field.
+pub fn merge_intersection(segments: &mut [RustSegment]) {
+    loop {
+        let index: HashMap<&str, usize> = segments
+            .iter()
+            .enumerate()
+            .map(|(i, s)| (s.name(), i))
+            .collect();
+        let mut merged = Vec::new();
+        for (i, segment) in segments.iter().enumerate() {
+            let RustSegment::Struct(s) = segment else {
+                continue;
+            };
+            // unknown members (e.g. literals consumed as tag) are not emitted
+            let flatten: Vec<_> = s.member.iter().filter(|m| !m.ty.is_unknown()).collect();
+            if flatten.len() < 2 || !flatten.iter().all(|m| is_flatten(m)) {
+                continue;
+            }
+            let member = flatten.into_iter().try_fold(Vec::new(), |mut acc, m| {
+                let RustType::Custom(tn) = &m.ty.ty else {
+                    return None;
+                };
+                let RustSegment::Struct(t) = &segments[*index.get(tn.name.as_str())?] else {
+                    return None;
+                };
+                if !t.attr.as_inner().is_empty() || t.member.iter().any(is_flatten) {
+                    return None;
+                }
+                for m in &t.member {
+                    acc.retain(|o: &RustStructMember| o.name != m.name);
+                    acc.push(m.clone());
+                }
+                Some(acc)
+            });
+            if let Some(member) = member {
+                merged.push((i, member));
+            }
+        }
+        if merged.is_empty() {
+            return;
+        }
+        for (i, member) in merged {
+            if let RustSegment::Struct(s) = &mut segments[i] {
+                s.member = member;
+            }
+        }
+    }
+}
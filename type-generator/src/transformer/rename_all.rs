@@ -1,53 +1,69 @@
 use crate::{
     case::{detect_case, CaseConvention},
-    ir::{RustEnumMemberKind, RustSegment, RustStructAttr, RustType, SerdeContainerAttr},
+    ir::{RustEnum, RustEnumMemberKind, RustSegment, RustStructAttr, RustType, SerdeContainerAttr},
+    visit::{self, VisitMut},
 };
 
-pub fn adapt_rename_all(segment: &mut RustSegment) -> Option<()> {
-    if let RustSegment::Enum(re) = segment {
-        let mut conv: Option<CaseConvention> = None;
-        for memb in &re.member {
-            let s = match &memb.kind {
-                RustEnumMemberKind::Nullary(v) => v,
-                RustEnumMemberKind::Unary(v) => v.to_ident(),
-                RustEnumMemberKind::UnaryNamed { variant_name, .. } => variant_name,
-            };
-            match conv.as_mut() {
-                Some(conv) => {
-                    let new = detect_case(s);
-                    conv.cast(new)?
-                }
-                None => {
-                    conv = Some(detect_case(s));
-                }
-            };
-        }
-        let rr = conv?.into_rename_rule();
-        if rr.is_pascal_case() {
-            return None;
+/// names variants in Pascal case, attributing `#[serde(rename_all)]` of the original case
+pub fn adapt_rename_all(segments: &mut [RustSegment]) {
+    struct RenameAll;
+    impl VisitMut for RenameAll {
+        fn visit_enum_mut(&mut self, e: &mut RustEnum) {
+            rename_all(e);
         }
-        for memb in &mut re.member {
-            match &mut memb.kind {
-                RustEnumMemberKind::Unary(v) => {
-                    if let Some(v) = v.as_mut_custom() {
-                        let type_name = v.to_owned();
-                        rr.convert_to_pascal(&mut v.name);
-                        memb.kind = RustEnumMemberKind::UnaryNamed {
-                            variant_name: v.name.to_owned(),
-                            type_name: RustType::Custom(type_name),
-                        };
-                    }
-                }
-                RustEnumMemberKind::Nullary(variant_name) => {
-                    rr.convert_to_pascal(variant_name);
-                }
-                RustEnumMemberKind::UnaryNamed { variant_name, .. } => {
-                    rr.convert_to_pascal(variant_name);
+    }
+    visit::walk_segments_mut(&mut RenameAll, segments);
+}
+
+fn rename_all(re: &mut RustEnum) -> Option<()> {
+    // numeric literal unions are not (de)serialized by name
+    if re.member.iter().any(|m| m.kind.is_discriminant()) {
+        return None;
+    }
+    let mut conv: Option<CaseConvention> = None;
+    for memb in &re.member {
+        let s = match &memb.kind {
+            RustEnumMemberKind::Nullary(v) => v,
+            RustEnumMemberKind::Unary(v) => v.to_ident(),
+            RustEnumMemberKind::UnaryNamed { variant_name, .. } => variant_name,
+            RustEnumMemberKind::Discriminant { .. } => unreachable!(),
+        };
+        match conv.as_mut() {
+            Some(conv) => {
+                let new = detect_case(s);
+                conv.cast(new)?
+            }
+            None => {
+                conv = Some(detect_case(s));
+            }
+        };
+    }
+    let rr = conv?.into_rename_rule();
+    if rr.is_pascal_case() {
+        return None;
+    }
+    for memb in &mut re.member {
+        match &mut memb.kind {
+            RustEnumMemberKind::Unary(v) => {
+                if let Some(v) = v.as_mut_custom() {
+                    let type_name = v.to_owned();
+                    rr.convert_to_pascal(&mut v.name);
+                    memb.kind = RustEnumMemberKind::UnaryNamed {
+                        variant_name: v.name.to_owned(),
+                        type_name: RustType::Custom(type_name),
+                    };
                 }
-            };
-        }
-        re.attr
-            .add_attr(RustStructAttr::Serde(SerdeContainerAttr::RenameAll(rr)));
+            }
+            RustEnumMemberKind::Nullary(variant_name) => {
+                rr.convert_to_pascal(variant_name);
+            }
+            RustEnumMemberKind::UnaryNamed { variant_name, .. } => {
+                rr.convert_to_pascal(variant_name);
+            }
+            RustEnumMemberKind::Discriminant { .. } => unreachable!(),
+        };
     }
+    re.attr
+        .add_attr(RustStructAttr::Serde(SerdeContainerAttr::RenameAll(rr)));
     Some(())
 }
@@ -0,0 +1,41 @@
+use crate::{
+    ir::{RustFieldAttr, RustSegment, RustStructMember, RustType, SerdeFieldAttr},
+    visit::{self, Visit, VisitMut},
+};
+
+/// make fields which may be both absent and `null` [`RustType::Nullable`]
+pub fn adapt_tri_state(segments: &mut [RustSegment]) {
+    struct TriState;
+    impl VisitMut for TriState {
+        fn visit_struct_member_mut(&mut self, m: &mut RustStructMember) {
+            if !(m.ty.is_optional && m.ty.is_nullable) {
+                return;
+            }
+            m.ty.ty = RustType::Nullable(Box::new(std::mem::take(&mut m.ty.ty)));
+            m.ty.is_optional = false;
+            m.ty.is_nullable = false;
+            m.attr
+                .add_attr(RustFieldAttr::Serde(SerdeFieldAttr::Default));
+            m.attr
+                .add_attr(RustFieldAttr::Serde(SerdeFieldAttr::SkipSerializingIf(
+                    "Nullable::is_absent".to_owned(),
+                )));
+        }
+    }
+    visit::walk_segments_mut(&mut TriState, segments);
+}
+
+pub fn uses_nullable(segments: &[RustSegment]) -> bool {
+    struct Uses(bool);
+    impl Visit for Uses {
+        fn visit_type(&mut self, ty: &RustType) {
+            if let RustType::Nullable(_) = ty {
+                self.0 = true;
+            }
+            visit::walk_type(self, ty);
+        }
+    }
+    let mut v = Uses(false);
+    visit::walk_segments(&mut v, segments);
+    v.0
+}
@@ -0,0 +1,217 @@
+//! traversal over [`ir`](crate::ir)
+//!
+//! Each method of [`Visit`] and [`VisitMut`] defaults to the `walk_*` function of the same node,
+//! which visits children of the node. Override some of the methods to inspect or rewrite the IR,
+//! and call the `walk_*` function in it to continue traversal.
+//!
+//! ```
+//! use github_webhook_type_generator::{
+//!     ir::{RustType, TypeName},
+//!     visit::{self, VisitMut},
+//! };
+//!
+//! /// use `u64` instead of `usize` for every number
+//! struct WidenUsize;
+//!
+//! impl VisitMut for WidenUsize {
+//!     fn visit_type_mut(&mut self, ty: &mut RustType) {
+//!         if let RustType::Number = ty {
+//!             *ty = RustType::Custom(TypeName::new("u64".to_owned()));
+//!         }
+//!         visit::walk_type_mut(self, ty);
+//!     }
+//! }
+//! ```
+
+use crate::ir::{
+    RustAlias, RustEnum, RustEnumMember, RustEnumMemberKind, RustSegment, RustStruct,
+    RustStructMember, RustType, TypeName,
+};
+
+pub trait Visit {
+    fn visit_segment(&mut self, segment: &RustSegment) {
+        walk_segment(self, segment)
+    }
+    fn visit_struct(&mut self, s: &RustStruct) {
+        walk_struct(self, s)
+    }
+    fn visit_struct_member(&mut self, member: &RustStructMember) {
+        walk_struct_member(self, member)
+    }
+    fn visit_enum(&mut self, e: &RustEnum) {
+        walk_enum(self, e)
+    }
+    fn visit_enum_member(&mut self, member: &RustEnumMember) {
+        walk_enum_member(self, member)
+    }
+    fn visit_alias(&mut self, a: &RustAlias) {
+        walk_alias(self, a)
+    }
+    fn visit_type(&mut self, ty: &RustType) {
+        walk_type(self, ty)
+    }
+    fn visit_type_name(&mut self, _type_name: &TypeName) {}
+}
+
+pub fn walk_segments<V: Visit + ?Sized>(v: &mut V, segments: &[RustSegment]) {
+    for segment in segments {
+        v.visit_segment(segment);
+    }
+}
+
+pub fn walk_segment<V: Visit + ?Sized>(v: &mut V, segment: &RustSegment) {
+    match segment {
+        RustSegment::Struct(s) => v.visit_struct(s),
+        RustSegment::Enum(e) => v.visit_enum(e),
+        RustSegment::Alias(a) => v.visit_alias(a),
+        RustSegment::Const(_) => (),
+    }
+}
+
+pub fn walk_struct<V: Visit + ?Sized>(v: &mut V, s: &RustStruct) {
+    for member in &s.member {
+        v.visit_struct_member(member);
+    }
+}
+
+pub fn walk_struct_member<V: Visit + ?Sized>(v: &mut V, member: &RustStructMember) {
+    v.visit_type(&member.ty.ty);
+}
+
+pub fn walk_enum<V: Visit + ?Sized>(v: &mut V, e: &RustEnum) {
+    for member in &e.member {
+        v.visit_enum_member(member);
+    }
+}
+
+pub fn walk_enum_member<V: Visit + ?Sized>(v: &mut V, member: &RustEnumMember) {
+    match &member.kind {
+        RustEnumMemberKind::Nullary(_) | RustEnumMemberKind::Discriminant { .. } => (),
+        RustEnumMemberKind::Unary(ty) | RustEnumMemberKind::UnaryNamed { type_name: ty, .. } => {
+            v.visit_type(ty)
+        }
+    }
+}
+
+pub fn walk_alias<V: Visit + ?Sized>(v: &mut V, a: &RustAlias) {
+    v.visit_type(&a.ty);
+}
+
+pub fn walk_type<V: Visit + ?Sized>(v: &mut V, ty: &RustType) {
+    match ty {
+        RustType::Custom(t) => v.visit_type_name(t),
+        RustType::Array(t) | RustType::Nullable(t) => v.visit_type(t),
+        RustType::Map(t1, t2) => {
+            v.visit_type(t1);
+            v.visit_type(t2);
+        }
+        RustType::Tuple(ts) => {
+            for t in ts {
+                v.visit_type(t);
+            }
+        }
+        RustType::String { .. }
+        | RustType::Number
+        | RustType::Float
+        | RustType::Boolean
+        | RustType::Unit
+        | RustType::Unknown
+        | RustType::UnknownLiteral
+        | RustType::Literal(_)
+        | RustType::UnknownIntersection => (),
+    }
+}
+
+pub trait VisitMut {
+    fn visit_segment_mut(&mut self, segment: &mut RustSegment) {
+        walk_segment_mut(self, segment)
+    }
+    fn visit_struct_mut(&mut self, s: &mut RustStruct) {
+        walk_struct_mut(self, s)
+    }
+    fn visit_struct_member_mut(&mut self, member: &mut RustStructMember) {
+        walk_struct_member_mut(self, member)
+    }
+    fn visit_enum_mut(&mut self, e: &mut RustEnum) {
+        walk_enum_mut(self, e)
+    }
+    fn visit_enum_member_mut(&mut self, member: &mut RustEnumMember) {
+        walk_enum_member_mut(self, member)
+    }
+    fn visit_alias_mut(&mut self, a: &mut RustAlias) {
+        walk_alias_mut(self, a)
+    }
+    fn visit_type_mut(&mut self, ty: &mut RustType) {
+        walk_type_mut(self, ty)
+    }
+    fn visit_type_name_mut(&mut self, _type_name: &mut TypeName) {}
+}
+
+pub fn walk_segments_mut<V: VisitMut + ?Sized>(v: &mut V, segments: &mut [RustSegment]) {
+    for segment in segments {
+        v.visit_segment_mut(segment);
+    }
+}
+
+pub fn walk_segment_mut<V: VisitMut + ?Sized>(v: &mut V, segment: &mut RustSegment) {
+    match segment {
+        RustSegment::Struct(s) => v.visit_struct_mut(s),
+        RustSegment::Enum(e) => v.visit_enum_mut(e),
+        RustSegment::Alias(a) => v.visit_alias_mut(a),
+        RustSegment::Const(_) => (),
+    }
+}
+
+pub fn walk_struct_mut<V: VisitMut + ?Sized>(v: &mut V, s: &mut RustStruct) {
+    for member in &mut s.member {
+        v.visit_struct_member_mut(member);
+    }
+}
+
+pub fn walk_struct_member_mut<V: VisitMut + ?Sized>(v: &mut V, member: &mut RustStructMember) {
+    v.visit_type_mut(&mut member.ty.ty);
+}
+
+pub fn walk_enum_mut<V: VisitMut + ?Sized>(v: &mut V, e: &mut RustEnum) {
+    for member in &mut e.member {
+        v.visit_enum_member_mut(member);
+    }
+}
+
+pub fn walk_enum_member_mut<V: VisitMut + ?Sized>(v: &mut V, member: &mut RustEnumMember) {
+    match &mut member.kind {
+        RustEnumMemberKind::Nullary(_) | RustEnumMemberKind::Discriminant { .. } => (),
+        RustEnumMemberKind::Unary(ty) | RustEnumMemberKind::UnaryNamed { type_name: ty, .. } => {
+            v.visit_type_mut(ty)
+        }
+    }
+}
+
+pub fn walk_alias_mut<V: VisitMut + ?Sized>(v: &mut V, a: &mut RustAlias) {
+    v.visit_type_mut(&mut a.ty);
+}
+
+pub fn walk_type_mut<V: VisitMut + ?Sized>(v: &mut V, ty: &mut RustType) {
+    match ty {
+        RustType::Custom(t) => v.visit_type_name_mut(t),
+        RustType::Array(t) | RustType::Nullable(t) => v.visit_type_mut(t),
+        RustType::Map(t1, t2) => {
+            v.visit_type_mut(t1);
+            v.visit_type_mut(t2);
+        }
+        RustType::Tuple(ts) => {
+            for t in ts {
+                v.visit_type_mut(t);
+            }
+        }
+        RustType::String { .. }
+        | RustType::Number
+        | RustType::Float
+        | RustType::Boolean
+        | RustType::Unit
+        | RustType::Unknown
+        | RustType::UnknownLiteral
+        | RustType::Literal(_)
+        | RustType::UnknownIntersection => (),
+    }
+}
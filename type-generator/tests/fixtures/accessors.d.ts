@@ -0,0 +1,30 @@
+export type Schema = StarEvent | PingEvent | MetaEvent;
+export type StarEvent = StarCreatedEvent | StarDeletedEvent;
+export interface StarCreatedEvent {
+  action: "created";
+  repository: Repository;
+  sender: User;
+}
+export interface StarDeletedEvent {
+  action: "deleted";
+  repository: Repository;
+  sender: User;
+}
+export interface PingEvent {
+  zen: string;
+  repository?: Repository;
+  sender?: User;
+}
+export interface MetaEvent {
+  hook_id: number;
+  sender: Hook;
+}
+export interface Repository {
+  full_name: string;
+}
+export interface User {
+  login: string;
+}
+export interface Hook {
+  id: number;
+}
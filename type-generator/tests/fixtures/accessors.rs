@@ -0,0 +1,205 @@
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum Schema<'a> {
+    #[serde(borrow = "'a")]
+    StarEvent(StarEvent<'a>),
+    PingEvent(PingEvent<'a>),
+    MetaEvent(MetaEvent),
+}
+#[derive(Debug, Deserialize)]
+#[serde(tag = "action")]
+#[serde(rename_all = "snake_case")]
+pub enum StarEvent<'a> {
+    #[serde(borrow = "'a")]
+    Created(StarCreatedEvent<'a>),
+    Deleted(StarDeletedEvent<'a>),
+}
+#[derive(Debug, Deserialize)]
+pub struct StarCreatedEvent<'a> {
+    #[serde(borrow = "'a")]
+    pub repository: Repository<'a>,
+    pub sender: User<'a>,
+}
+#[derive(Debug, Deserialize)]
+pub struct StarDeletedEvent<'a> {
+    #[serde(borrow = "'a")]
+    pub repository: Repository<'a>,
+    pub sender: User<'a>,
+}
+#[derive(Debug, Deserialize)]
+pub struct PingEvent<'a> {
+    pub zen: &'a str,
+    pub repository: Option<Repository<'a>>,
+    pub sender: Option<User<'a>>,
+}
+#[derive(Debug, Deserialize)]
+pub struct MetaEvent {
+    pub hook_id: usize,
+    pub sender: Hook,
+}
+#[derive(Debug, Deserialize)]
+pub struct Repository<'a> {
+    pub full_name: &'a str,
+}
+#[derive(Debug, Deserialize)]
+pub struct User<'a> {
+    pub login: &'a str,
+}
+#[derive(Debug, Deserialize)]
+pub struct Hook {
+    pub id: usize,
+}
+#[doc = "types having `repository`"]
+pub trait HasRepository<'a> {
+    fn repository(&self) -> Option<&Repository<'a>>;
+}
+impl<'a> HasRepository<'a> for StarCreatedEvent<'a> {
+    fn repository(&self) -> Option<&Repository<'a>> {
+        Some(&self.repository)
+    }
+}
+impl<'a> HasRepository<'a> for StarDeletedEvent<'a> {
+    fn repository(&self) -> Option<&Repository<'a>> {
+        Some(&self.repository)
+    }
+}
+impl<'a> HasRepository<'a> for PingEvent<'a> {
+    fn repository(&self) -> Option<&Repository<'a>> {
+        self.repository.as_ref()
+    }
+}
+impl<'a> HasRepository<'a> for StarEvent<'a> {
+    fn repository(&self) -> Option<&Repository<'a>> {
+        match self {
+            Self::Created(e) => e.repository(),
+            Self::Deleted(e) => e.repository(),
+        }
+    }
+}
+#[doc = "types having `sender`"]
+pub trait HasSender<'a> {
+    fn sender(&self) -> Option<&User<'a>>;
+}
+impl<'a> HasSender<'a> for StarCreatedEvent<'a> {
+    fn sender(&self) -> Option<&User<'a>> {
+        Some(&self.sender)
+    }
+}
+impl<'a> HasSender<'a> for StarDeletedEvent<'a> {
+    fn sender(&self) -> Option<&User<'a>> {
+        Some(&self.sender)
+    }
+}
+impl<'a> HasSender<'a> for PingEvent<'a> {
+    fn sender(&self) -> Option<&User<'a>> {
+        self.sender.as_ref()
+    }
+}
+impl<'a> HasSender<'a> for StarEvent<'a> {
+    fn sender(&self) -> Option<&User<'a>> {
+        match self {
+            Self::Created(e) => e.sender(),
+            Self::Deleted(e) => e.sender(),
+        }
+    }
+}
+#[doc = "`action` of [`StarEvent`]"]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StarEventAction {
+    Created,
+    Deleted,
+}
+impl StarEventAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Created => "created",
+            Self::Deleted => "deleted",
+        }
+    }
+}
+impl std::fmt::Display for StarEventAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+impl<'a> StarEvent<'a> {
+    pub fn action(&self) -> StarEventAction {
+        match self {
+            Self::Created(_) => StarEventAction::Created,
+            Self::Deleted(_) => StarEventAction::Deleted,
+        }
+    }
+}
+impl<'a> Schema<'a> {
+    pub fn repository(&self) -> Option<&Repository<'a>> {
+        match self {
+            Self::StarEvent(e) => e.repository(),
+            Self::PingEvent(e) => e.repository(),
+            Self::MetaEvent(_) => None,
+        }
+    }
+    pub fn sender(&self) -> Option<&User<'a>> {
+        match self {
+            Self::StarEvent(e) => e.sender(),
+            Self::PingEvent(e) => e.sender(),
+            Self::MetaEvent(_) => None,
+        }
+    }
+    #[doc = r" events [`Self::deserialize_event`] accepts"]
+    pub const EVENT_NAMES: &'static [&'static str] = &["star", "ping", "meta"];
+    #[doc = r" deserialize the payload of `event` (as in `X-GitHub-Event` header) into its variant,"]
+    #[doc = r" which reports errors better than trying every variant. `None` for unknown events."]
+    pub fn deserialize_event<'de, D: serde::Deserializer<'de>>(
+        event: &str,
+        deserializer: D,
+    ) -> Result<Option<Self>, D::Error>
+    where
+        'de: 'a,
+    {
+        Ok(Some(match event {
+            "star" => Self::StarEvent(serde::Deserialize::deserialize(deserializer)?),
+            "ping" => Self::PingEvent(serde::Deserialize::deserialize(deserializer)?),
+            "meta" => Self::MetaEvent(serde::Deserialize::deserialize(deserializer)?),
+            _ => return Ok(None),
+        }))
+    }
+    #[doc = r" like [`Self::deserialize_event`], but deserializes the variant of `action` directly"]
+    #[doc = r" for events tagged by it, so that errors are not hidden by the buffering of the tag"]
+    pub fn deserialize_event_action<'de, D: serde::Deserializer<'de>>(
+        event: &str,
+        action: Option<&str>,
+        deserializer: D,
+    ) -> Result<Option<Self>, D::Error>
+    where
+        'de: 'a,
+    {
+        Ok(Some(match (event, action) {
+            ("star", Some("created")) => Self::StarEvent(StarEvent::Created(
+                serde::Deserialize::deserialize(deserializer)?,
+            )),
+            ("star", Some("deleted")) => Self::StarEvent(StarEvent::Deleted(
+                serde::Deserialize::deserialize(deserializer)?,
+            )),
+            ("star", _) => Self::StarEvent(serde::Deserialize::deserialize(deserializer)?),
+            ("ping", _) => Self::PingEvent(serde::Deserialize::deserialize(deserializer)?),
+            ("meta", _) => Self::MetaEvent(serde::Deserialize::deserialize(deserializer)?),
+            _ => return Ok(None),
+        }))
+    }
+    #[doc = r" name of the event as in `X-GitHub-Event` header, e.g. `pull_request`"]
+    pub fn event_name(&self) -> &'static str {
+        match self {
+            Self::StarEvent(_) => "star",
+            Self::PingEvent(_) => "ping",
+            Self::MetaEvent(_) => "meta",
+        }
+    }
+    #[doc = r" `action` of the event, if the event has actions"]
+    pub fn action(&self) -> Option<&'static str> {
+        match self {
+            Self::StarEvent(e) => Some(e.action().as_str()),
+            Self::PingEvent(_) => None,
+            Self::MetaEvent(_) => None,
+        }
+    }
+}
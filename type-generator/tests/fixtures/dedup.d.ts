@@ -0,0 +1,31 @@
+export interface User {
+  login: string;
+  id: number;
+}
+export interface StarCreatedEvent {
+  /**
+   * The user who starred.
+   */
+  sender: {
+    login: string;
+    id: number;
+  };
+  starred_by: {
+    login: string;
+    id: number;
+  };
+  repository: {
+    name: string;
+    owner: {
+      name: string;
+    };
+  };
+}
+export interface StarDeletedEvent {
+  repository: {
+    name: string;
+    owner: {
+      name: string;
+    };
+  };
+}
@@ -0,0 +1,27 @@
+#[derive(Debug, Deserialize)]
+pub struct User<'a> {
+    pub login: &'a str,
+    pub id: usize,
+}
+#[derive(Debug, Deserialize)]
+pub struct StarCreatedEventRepositoryOwner<'a> {
+    pub name: &'a str,
+}
+#[derive(Debug, Deserialize)]
+pub struct StarCreatedEventRepository<'a> {
+    pub name: &'a str,
+    pub owner: StarCreatedEventRepositoryOwner<'a>,
+}
+#[derive(Debug, Deserialize)]
+pub struct StarCreatedEvent<'a> {
+    #[doc = "The user who starred."]
+    #[serde(borrow = "'a")]
+    pub sender: User<'a>,
+    pub starred_by: User<'a>,
+    pub repository: StarCreatedEventRepository<'a>,
+}
+#[derive(Debug, Deserialize)]
+pub struct StarDeletedEvent<'a> {
+    #[serde(borrow = "'a")]
+    pub repository: StarCreatedEventRepository<'a>,
+}
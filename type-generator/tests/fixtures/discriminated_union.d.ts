@@ -0,0 +1,27 @@
+export type IssuesEvent = IssuesOpenedEvent | IssuesClosedEvent | IssuesLabeledEvent;
+
+export interface IssuesOpenedEvent {
+  action: "opened";
+  number: number;
+}
+export interface IssuesClosedEvent {
+  action: "closed";
+  number: number;
+}
+export interface IssuesLabeledEvent {
+  action: "labeled";
+  number: number;
+  label?: string;
+}
+export interface Commit {
+  verification:
+    | {
+        verified: true;
+        reason: string;
+        signature: string;
+      }
+    | {
+        verified: false;
+        reason: string;
+      };
+}
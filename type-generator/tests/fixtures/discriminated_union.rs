@@ -0,0 +1,95 @@
+#[derive(Debug, Deserialize)]
+#[serde(tag = "action")]
+#[serde(rename_all = "snake_case")]
+pub enum IssuesEvent<'a> {
+    Opened(IssuesOpenedEvent),
+    Closed(IssuesClosedEvent),
+    #[serde(borrow = "'a")]
+    Labeled(IssuesLabeledEvent<'a>),
+}
+#[derive(Debug, Deserialize)]
+pub struct IssuesOpenedEvent {
+    pub number: usize,
+}
+#[derive(Debug, Deserialize)]
+pub struct IssuesClosedEvent {
+    pub number: usize,
+}
+#[derive(Debug, Deserialize)]
+pub struct IssuesLabeledEvent<'a> {
+    pub number: usize,
+    pub label: Option<&'a str>,
+}
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CommitVerification1Verified;
+impl CommitVerification1Verified {
+    pub const VALUE: bool = true;
+}
+impl<'de> serde::Deserialize<'de> for CommitVerification1Verified {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = <bool as serde::Deserialize>::deserialize(deserializer)?;
+        if value == Self::VALUE {
+            Ok(Self)
+        } else {
+            Err(serde::de::Error::invalid_value(
+                serde::de::Unexpected::Bool(value),
+                &"true",
+            ))
+        }
+    }
+}
+impl serde::Serialize for CommitVerification1Verified {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bool(Self::VALUE)
+    }
+}
+#[derive(Debug, Deserialize)]
+pub struct CommitVerification1<'a> {
+    pub verified: CommitVerification1Verified,
+    pub signature: &'a str,
+}
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CommitVerification2Verified;
+impl CommitVerification2Verified {
+    pub const VALUE: bool = false;
+}
+impl<'de> serde::Deserialize<'de> for CommitVerification2Verified {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = <bool as serde::Deserialize>::deserialize(deserializer)?;
+        if value == Self::VALUE {
+            Ok(Self)
+        } else {
+            Err(serde::de::Error::invalid_value(
+                serde::de::Unexpected::Bool(value),
+                &"false",
+            ))
+        }
+    }
+}
+impl serde::Serialize for CommitVerification2Verified {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bool(Self::VALUE)
+    }
+}
+#[derive(Debug, Deserialize)]
+pub struct CommitVerification2 {
+    pub verified: CommitVerification2Verified,
+}
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum CommitVerificationDistinctUnion<'a> {
+    #[serde(borrow = "'a")]
+    CommitVerification1(CommitVerification1<'a>),
+    CommitVerification2(CommitVerification2),
+}
+#[derive(Debug, Deserialize)]
+pub struct CommitVerification<'a> {
+    pub reason: &'a str,
+    #[serde(flatten)]
+    pub distinct: CommitVerificationDistinctUnion<'a>,
+}
+#[derive(Debug, Deserialize)]
+pub struct Commit<'a> {
+    #[serde(borrow = "'a")]
+    pub verification: CommitVerification<'a>,
+}
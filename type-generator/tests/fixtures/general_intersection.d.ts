@@ -0,0 +1,17 @@
+export interface Base {
+  id: number;
+  name: string;
+}
+export interface Timestamps {
+  created_at: string;
+  updated_at: string;
+}
+/** merged from two interfaces */
+export type Merged = Base & Timestamps;
+/** literal first */
+export type Labeled = { label: string } & Base;
+export type Nested = Base & (Timestamps & { deleted: boolean });
+export interface Extra {
+  extra: string[];
+}
+export type Event = (Base & { kind: "a" }) | (Timestamps & Extra & { kind: "b" });
@@ -0,0 +1,53 @@
+#[derive(Debug, Deserialize)]
+pub struct Base<'a> {
+    pub id: usize,
+    pub name: &'a str,
+}
+#[derive(Debug, Deserialize)]
+pub struct Timestamps<'a> {
+    pub created_at: &'a str,
+    pub updated_at: &'a str,
+}
+#[derive(Debug, Deserialize)]
+#[doc = "merged from two interfaces"]
+pub struct Merged<'a> {
+    pub id: usize,
+    pub name: &'a str,
+    pub created_at: &'a str,
+    pub updated_at: &'a str,
+}
+#[derive(Debug, Deserialize)]
+#[doc = "literal first"]
+pub struct Labeled<'a> {
+    pub label: &'a str,
+    #[serde(flatten)]
+    pub base: Base<'a>,
+}
+#[derive(Debug, Deserialize)]
+pub struct Nested<'a> {
+    pub deleted: bool,
+    #[serde(flatten)]
+    #[serde(borrow = "'a")]
+    pub base: Base<'a>,
+    #[serde(flatten)]
+    pub timestamps: Timestamps<'a>,
+}
+#[derive(Debug, Deserialize)]
+pub struct Extra<'a> {
+    #[serde(borrow = "'a")]
+    pub extra: Vec<&'a str>,
+}
+#[derive(Debug, Deserialize)]
+pub struct Event2<'a> {
+    pub created_at: &'a str,
+    pub updated_at: &'a str,
+    pub extra: Vec<&'a str>,
+}
+#[derive(Debug, Deserialize)]
+#[serde(tag = "kind")]
+#[serde(rename_all = "snake_case")]
+pub enum Event<'a> {
+    #[serde(borrow = "'a")]
+    A(Base<'a>),
+    B(Event2<'a>),
+}
@@ -0,0 +1,23 @@
+export interface Label {
+  id: number;
+  name: string;
+  color?: string;
+  type: "label";
+}
+export interface Issue {
+  labels: Array<Label>;
+  assignees: ReadonlyArray<string>;
+  metadata: Record<string, string>;
+  reactions: Record<"+1" | "-1", number>;
+  /** only the identifying fields */
+  label_ref: Pick<Label, "id" | "name">;
+  changes: Partial<Label>;
+  /** qualified name refers to the last identifier */
+  author: Github.User;
+}
+export type LabelUpdate = Partial<Omit<Label, "id" | "type">>;
+export type LabelMap = Record<string, Label>;
+export type Tags = Array<{ tag: string }>;
+export interface User {
+  login: string;
+}
@@ -0,0 +1,72 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct LabelType;
+impl LabelType {
+    pub const VALUE: &'static str = "label";
+}
+impl<'de> serde::Deserialize<'de> for LabelType {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = <std::borrow::Cow<'de, str> as serde::Deserialize>::deserialize(deserializer)?;
+        if value == Self::VALUE {
+            Ok(Self)
+        } else {
+            Err(serde::de::Error::invalid_value(
+                serde::de::Unexpected::Str(&value),
+                &Self::VALUE,
+            ))
+        }
+    }
+}
+impl serde::Serialize for LabelType {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(Self::VALUE)
+    }
+}
+#[derive(Debug, Deserialize)]
+pub struct Label<'a> {
+    pub id: usize,
+    pub name: &'a str,
+    pub color: Option<&'a str>,
+    #[serde(rename = "type")]
+    pub type_: LabelType,
+}
+#[derive(Debug, Deserialize)]
+pub struct IssueLabelRef<'a> {
+    pub id: usize,
+    pub name: &'a str,
+}
+#[derive(Debug, Deserialize)]
+pub struct IssueChanges<'a> {
+    pub id: Option<usize>,
+    pub name: Option<&'a str>,
+    pub color: Option<&'a str>,
+    #[serde(rename = "type")]
+    pub type_: Option<LabelType>,
+}
+#[derive(Debug, Deserialize)]
+pub struct Issue<'a> {
+    #[serde(borrow = "'a")]
+    pub labels: Vec<Label<'a>>,
+    pub assignees: Vec<&'a str>,
+    pub metadata: HashMap<&'a str, &'a str>,
+    pub reactions: HashMap<&'a str, usize>,
+    #[doc = "only the identifying fields"]
+    pub label_ref: IssueLabelRef<'a>,
+    pub changes: IssueChanges<'a>,
+    #[doc = "qualified name refers to the last identifier"]
+    pub author: User<'a>,
+}
+#[derive(Debug, Deserialize)]
+pub struct LabelUpdate<'a> {
+    pub name: Option<&'a str>,
+    pub color: Option<&'a str>,
+}
+pub type LabelMap<'a> = HashMap<&'a str, Label<'a>>;
+#[derive(Debug, Deserialize)]
+pub struct Tags1<'a> {
+    pub tag: &'a str,
+}
+pub type Tags<'a> = Vec<Tags1<'a>>;
+#[derive(Debug, Deserialize)]
+pub struct User<'a> {
+    pub login: &'a str,
+}
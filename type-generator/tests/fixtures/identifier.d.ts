@@ -0,0 +1,13 @@
+export interface Weird {
+  async: boolean;
+  match: string;
+  mod?: number;
+  super: string;
+  "foo.bar": string;
+  "x-github-event": string;
+  "1st": number;
+  foo_bar: string;
+  "foo-bar": number;
+  "": string;
+  kind: "self" | "Self" | "" | "in-progress" | "in_progress";
+}
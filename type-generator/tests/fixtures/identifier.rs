@@ -0,0 +1,33 @@
+#[derive(Debug, Deserialize)]
+pub enum WeirdKind {
+    #[serde(rename = "")]
+    Empty,
+    #[serde(rename = "Self")]
+    Self_,
+    #[serde(rename = "in-progress")]
+    InProgress,
+    #[serde(rename = "in_progress")]
+    InProgress2,
+    #[serde(rename = "self")]
+    Self_2,
+}
+#[derive(Debug, Deserialize)]
+pub struct Weird<'a> {
+    pub r#async: bool,
+    pub r#match: &'a str,
+    pub r#mod: Option<usize>,
+    #[serde(rename = "super")]
+    pub super_: &'a str,
+    #[serde(rename = "foo.bar")]
+    pub foo_bar_2: &'a str,
+    #[serde(rename = "x-github-event")]
+    pub x_github_event: &'a str,
+    #[serde(rename = "1st")]
+    pub _1st: usize,
+    pub foo_bar: &'a str,
+    #[serde(rename = "foo-bar")]
+    pub foo_bar_3: usize,
+    #[serde(rename = "")]
+    pub field: &'a str,
+    pub kind: WeirdKind,
+}
@@ -0,0 +1,9 @@
+export interface Repository {
+  name: string;
+  custom_properties: {
+    [k: string]: unknown;
+  };
+  permissions?: {
+    [k: string]: boolean;
+  };
+}
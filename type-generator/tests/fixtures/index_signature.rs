@@ -0,0 +1,6 @@
+#[derive(Debug, Deserialize)]
+pub struct Repository<'a> {
+    pub name: &'a str,
+    pub custom_properties: HashMap<&'a str, Value>,
+    pub permissions: Option<HashMap<&'a str, bool>>,
+}
@@ -0,0 +1,16 @@
+export interface Issue {
+  title: string;
+  state?: "open" | "closed";
+  closed_at: string | null;
+}
+export interface IssuesClosedEvent {
+  issue: Issue & {
+    state: "closed";
+    closed_at: string;
+  };
+}
+export interface IssuesReopenedEvent {
+  issue: Issue & {
+    state: "open";
+  };
+}
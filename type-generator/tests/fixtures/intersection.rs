@@ -0,0 +1,29 @@
+#[derive(Debug, Deserialize)]
+pub enum IssueState {
+    #[serde(rename = "closed")]
+    Closed,
+    #[serde(rename = "open")]
+    Open,
+}
+#[derive(Debug, Deserialize)]
+pub struct Issue<'a> {
+    pub title: &'a str,
+    pub state: Option<IssueState>,
+    pub closed_at: Option<&'a str>,
+}
+#[derive(Debug, Deserialize)]
+pub struct IssuesClosedEventIssue<'a> {
+    pub closed_at: &'a str,
+    #[serde(flatten)]
+    pub issue: Issue<'a>,
+}
+#[derive(Debug, Deserialize)]
+pub struct IssuesClosedEvent<'a> {
+    #[serde(borrow = "'a")]
+    pub issue: IssuesClosedEventIssue<'a>,
+}
+#[derive(Debug, Deserialize)]
+pub struct IssuesReopenedEvent<'a> {
+    #[serde(borrow = "'a")]
+    pub issue: Issue<'a>,
+}
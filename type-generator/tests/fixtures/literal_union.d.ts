@@ -0,0 +1,16 @@
+export interface User {
+  login: string;
+  type: "Bot" | "User" | "Organization";
+}
+export interface Repository {
+  visibility: "public" | "private" | "internal";
+  merge_commit_title?: "PR_TITLE" | "MERGE_MESSAGE";
+}
+export interface Team {
+  privacy: "secret" | "closed";
+  notification_setting: "notifications_enabled" | "notifications_disabled";
+  /**
+   * same set of literals as `Team.privacy`
+   */
+  parent_privacy: "closed" | "secret";
+}
@@ -0,0 +1,55 @@
+#[derive(Debug, Deserialize)]
+pub enum UserType {
+    Bot,
+    Organization,
+    User,
+}
+#[derive(Debug, Deserialize)]
+pub struct User<'a> {
+    pub login: &'a str,
+    #[serde(rename = "type")]
+    pub type_: UserType,
+}
+#[derive(Debug, Deserialize)]
+pub enum RepositoryVisibility {
+    #[serde(rename = "internal")]
+    Internal,
+    #[serde(rename = "private")]
+    Private,
+    #[serde(rename = "public")]
+    Public,
+}
+#[derive(Debug, Deserialize)]
+pub enum RepositoryMergeCommitTitle {
+    #[serde(rename = "MERGE_MESSAGE")]
+    MergeMessage,
+    #[serde(rename = "PR_TITLE")]
+    PrTitle,
+}
+#[derive(Debug, Deserialize)]
+pub struct Repository {
+    pub visibility: RepositoryVisibility,
+    pub merge_commit_title: Option<RepositoryMergeCommitTitle>,
+}
+#[derive(Debug, Deserialize)]
+pub enum TeamPrivacy {
+    #[serde(rename = "closed")]
+    Closed,
+    #[serde(rename = "secret")]
+    Secret,
+}
+#[derive(Debug, Deserialize)]
+pub enum TeamNotificationSetting {
+    #[serde(rename = "notifications_disabled")]
+    NotificationsDisabled,
+    #[serde(rename = "notifications_enabled")]
+    NotificationsEnabled,
+}
+pub type TeamParentPrivacy = TeamPrivacy;
+#[derive(Debug, Deserialize)]
+pub struct Team {
+    pub privacy: TeamPrivacy,
+    pub notification_setting: TeamNotificationSetting,
+    #[doc = "same set of literals as `Team.privacy`"]
+    pub parent_privacy: TeamParentPrivacy,
+}
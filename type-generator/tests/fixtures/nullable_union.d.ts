@@ -0,0 +1,16 @@
+export interface PullRequest {
+  body: string | null;
+  merged_at?: string | null;
+  milestone: Milestone | null;
+  requested_reviewers: (User | Team)[];
+  assignee: User | Team | null;
+}
+export interface Milestone {
+  title: string;
+}
+export interface User {
+  login: string;
+}
+export interface Team {
+  slug: string;
+}
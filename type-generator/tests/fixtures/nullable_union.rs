@@ -0,0 +1,34 @@
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum PullRequestRequestedReviewersUnion<'a> {
+    #[serde(borrow = "'a")]
+    User(User<'a>),
+    Team(Team<'a>),
+}
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum PullRequestAssigneeUnion<'a> {
+    #[serde(borrow = "'a")]
+    User(User<'a>),
+    Team(Team<'a>),
+}
+#[derive(Debug, Deserialize)]
+pub struct PullRequest<'a> {
+    pub body: Option<&'a str>,
+    pub merged_at: Option<&'a str>,
+    pub milestone: Option<Milestone<'a>>,
+    pub requested_reviewers: Vec<PullRequestRequestedReviewersUnion<'a>>,
+    pub assignee: Option<PullRequestAssigneeUnion<'a>>,
+}
+#[derive(Debug, Deserialize)]
+pub struct Milestone<'a> {
+    pub title: &'a str,
+}
+#[derive(Debug, Deserialize)]
+pub struct User<'a> {
+    pub login: &'a str,
+}
+#[derive(Debug, Deserialize)]
+pub struct Team<'a> {
+    pub slug: &'a str,
+}
@@ -0,0 +1,11 @@
+export interface Check {
+  /** exit status */
+  status: 0 | 1 | 2;
+  offset: -1 | 0 | 1;
+  port: 80 | 443 | 8080;
+  weight: 0.5 | 1;
+  enabled: true | false;
+  level?: 1 | 2 | null;
+  version: 3;
+  locked: true;
+}
@@ -0,0 +1,156 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum CheckStatus {
+    N0 = 0,
+    N1 = 1,
+    N2 = 2,
+}
+impl<'de> serde::Deserialize<'de> for CheckStatus {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match <i64 as serde::Deserialize>::deserialize(deserializer)? {
+            0 => Ok(Self::N0),
+            1 => Ok(Self::N1),
+            2 => Ok(Self::N2),
+            value => Err(serde::de::Error::invalid_value(
+                serde::de::Unexpected::Signed(value),
+                &"one of 0, 1, 2",
+            )),
+        }
+    }
+}
+impl serde::Serialize for CheckStatus {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_i64(*self as i64)
+    }
+}
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(i8)]
+pub enum CheckOffset {
+    NMinus1 = -1,
+    N0 = 0,
+    N1 = 1,
+}
+impl<'de> serde::Deserialize<'de> for CheckOffset {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match <i64 as serde::Deserialize>::deserialize(deserializer)? {
+            -1 => Ok(Self::NMinus1),
+            0 => Ok(Self::N0),
+            1 => Ok(Self::N1),
+            value => Err(serde::de::Error::invalid_value(
+                serde::de::Unexpected::Signed(value),
+                &"one of -1, 0, 1",
+            )),
+        }
+    }
+}
+impl serde::Serialize for CheckOffset {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_i64(*self as i64)
+    }
+}
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u16)]
+pub enum CheckPort {
+    N80 = 80,
+    N443 = 443,
+    N8080 = 8080,
+}
+impl<'de> serde::Deserialize<'de> for CheckPort {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match <i64 as serde::Deserialize>::deserialize(deserializer)? {
+            80 => Ok(Self::N80),
+            443 => Ok(Self::N443),
+            8080 => Ok(Self::N8080),
+            value => Err(serde::de::Error::invalid_value(
+                serde::de::Unexpected::Signed(value),
+                &"one of 80, 443, 8080",
+            )),
+        }
+    }
+}
+impl serde::Serialize for CheckPort {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_i64(*self as i64)
+    }
+}
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum CheckLevel {
+    N1 = 1,
+    N2 = 2,
+}
+impl<'de> serde::Deserialize<'de> for CheckLevel {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match <i64 as serde::Deserialize>::deserialize(deserializer)? {
+            1 => Ok(Self::N1),
+            2 => Ok(Self::N2),
+            value => Err(serde::de::Error::invalid_value(
+                serde::de::Unexpected::Signed(value),
+                &"one of 1, 2",
+            )),
+        }
+    }
+}
+impl serde::Serialize for CheckLevel {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_i64(*self as i64)
+    }
+}
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CheckVersion;
+impl CheckVersion {
+    pub const VALUE: i64 = 3;
+}
+impl<'de> serde::Deserialize<'de> for CheckVersion {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = <i64 as serde::Deserialize>::deserialize(deserializer)?;
+        if value == Self::VALUE {
+            Ok(Self)
+        } else {
+            Err(serde::de::Error::invalid_value(
+                serde::de::Unexpected::Signed(value),
+                &"3",
+            ))
+        }
+    }
+}
+impl serde::Serialize for CheckVersion {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_i64(Self::VALUE)
+    }
+}
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CheckLocked;
+impl CheckLocked {
+    pub const VALUE: bool = true;
+}
+impl<'de> serde::Deserialize<'de> for CheckLocked {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = <bool as serde::Deserialize>::deserialize(deserializer)?;
+        if value == Self::VALUE {
+            Ok(Self)
+        } else {
+            Err(serde::de::Error::invalid_value(
+                serde::de::Unexpected::Bool(value),
+                &"true",
+            ))
+        }
+    }
+}
+impl serde::Serialize for CheckLocked {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bool(Self::VALUE)
+    }
+}
+#[derive(Debug, Deserialize)]
+pub struct Check {
+    #[doc = "exit status"]
+    pub status: CheckStatus,
+    pub offset: CheckOffset,
+    pub port: CheckPort,
+    pub weight: f64,
+    pub enabled: bool,
+    pub level: Option<CheckLevel>,
+    pub version: CheckVersion,
+    pub locked: CheckLocked,
+}
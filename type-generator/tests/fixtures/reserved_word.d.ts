@@ -0,0 +1,10 @@
+export interface Reactions {
+  "+1": number;
+  "-1": number;
+  laugh: number;
+}
+export interface PushEvent {
+  ref: string;
+  type: string;
+  self?: string;
+}
@@ -0,0 +1,17 @@
+#[derive(Debug, Deserialize)]
+pub struct Reactions {
+    #[serde(rename = "+1")]
+    pub plus_1: usize,
+    #[serde(rename = "-1")]
+    pub minus_1: usize,
+    pub laugh: usize,
+}
+#[derive(Debug, Deserialize)]
+pub struct PushEvent<'a> {
+    #[serde(rename = "ref")]
+    pub ref_: &'a str,
+    #[serde(rename = "type")]
+    pub type_: &'a str,
+    #[serde(rename = "self")]
+    pub self_: Option<&'a str>,
+}
@@ -0,0 +1,12 @@
+export type Schema = StarEvent | WatchEvent;
+export interface StarEvent {
+  action: "created";
+  sender: User;
+}
+export interface WatchEvent {
+  action: "created";
+  sender: User;
+}
+export interface User {
+  login: string;
+}
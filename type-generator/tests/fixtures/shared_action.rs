@@ -0,0 +1,115 @@
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum Schema<'a> {
+    #[serde(borrow = "'a")]
+    StarEvent(StarEvent<'a>),
+    WatchEvent(WatchEvent<'a>),
+}
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct StarEventAction;
+impl StarEventAction {
+    pub const VALUE: &'static str = "created";
+}
+impl<'de> serde::Deserialize<'de> for StarEventAction {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = <std::borrow::Cow<'de, str> as serde::Deserialize>::deserialize(deserializer)?;
+        if value == Self::VALUE {
+            Ok(Self)
+        } else {
+            Err(serde::de::Error::invalid_value(
+                serde::de::Unexpected::Str(&value),
+                &Self::VALUE,
+            ))
+        }
+    }
+}
+impl serde::Serialize for StarEventAction {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(Self::VALUE)
+    }
+}
+#[derive(Debug, Deserialize)]
+pub struct StarEvent<'a> {
+    pub action: StarEventAction,
+    #[serde(borrow = "'a")]
+    pub sender: User<'a>,
+}
+#[derive(Debug, Deserialize)]
+pub struct WatchEvent<'a> {
+    pub action: StarEventAction,
+    #[serde(borrow = "'a")]
+    pub sender: User<'a>,
+}
+#[derive(Debug, Deserialize)]
+pub struct User<'a> {
+    pub login: &'a str,
+}
+#[doc = "types having `sender`"]
+pub trait HasSender<'a> {
+    fn sender(&self) -> Option<&User<'a>>;
+}
+impl<'a> HasSender<'a> for StarEvent<'a> {
+    fn sender(&self) -> Option<&User<'a>> {
+        Some(&self.sender)
+    }
+}
+impl<'a> HasSender<'a> for WatchEvent<'a> {
+    fn sender(&self) -> Option<&User<'a>> {
+        Some(&self.sender)
+    }
+}
+impl<'a> Schema<'a> {
+    pub fn sender(&self) -> Option<&User<'a>> {
+        match self {
+            Self::StarEvent(e) => e.sender(),
+            Self::WatchEvent(e) => e.sender(),
+        }
+    }
+    #[doc = r" events [`Self::deserialize_event`] accepts"]
+    pub const EVENT_NAMES: &'static [&'static str] = &["star", "watch"];
+    #[doc = r" deserialize the payload of `event` (as in `X-GitHub-Event` header) into its variant,"]
+    #[doc = r" which reports errors better than trying every variant. `None` for unknown events."]
+    pub fn deserialize_event<'de, D: serde::Deserializer<'de>>(
+        event: &str,
+        deserializer: D,
+    ) -> Result<Option<Self>, D::Error>
+    where
+        'de: 'a,
+    {
+        Ok(Some(match event {
+            "star" => Self::StarEvent(serde::Deserialize::deserialize(deserializer)?),
+            "watch" => Self::WatchEvent(serde::Deserialize::deserialize(deserializer)?),
+            _ => return Ok(None),
+        }))
+    }
+    #[doc = r" like [`Self::deserialize_event`], but deserializes the variant of `action` directly"]
+    #[doc = r" for events tagged by it, so that errors are not hidden by the buffering of the tag"]
+    pub fn deserialize_event_action<'de, D: serde::Deserializer<'de>>(
+        event: &str,
+        action: Option<&str>,
+        deserializer: D,
+    ) -> Result<Option<Self>, D::Error>
+    where
+        'de: 'a,
+    {
+        Ok(Some(match (event, action) {
+            ("star", _) => Self::StarEvent(serde::Deserialize::deserialize(deserializer)?),
+            ("watch", _) => Self::WatchEvent(serde::Deserialize::deserialize(deserializer)?),
+            _ => return Ok(None),
+        }))
+    }
+    #[doc = r" name of the event as in `X-GitHub-Event` header, e.g. `pull_request`"]
+    pub fn event_name(&self) -> &'static str {
+        match self {
+            Self::StarEvent(_) => "star",
+            Self::WatchEvent(_) => "watch",
+        }
+    }
+    #[doc = r" `action` of the event, if the event has actions"]
+    pub fn action(&self) -> Option<&'static str> {
+        match self {
+            Self::StarEvent(_) => None,
+            Self::WatchEvent(_) => None,
+        }
+    }
+}
@@ -0,0 +1,37 @@
+pub type Schema<'a> = Repository<'a>;
+#[doc = "RFC 3339 date-time, e.g. `2024-01-01T12:00:00Z`"]
+pub type DateTime<'a> = &'a str;
+#[doc = "URI"]
+pub type Uri<'a> = &'a str;
+#[doc = "RFC 6570 URI template"]
+pub type UriTemplate<'a> = &'a str;
+#[doc = "RFC 3339 full-date, e.g. `2024-01-01`"]
+pub type Date<'a> = &'a str;
+#[doc = "email address"]
+pub type Email<'a> = &'a str;
+#[derive(Debug, Deserialize)]
+pub struct Repository<'a> {
+    pub name: &'a str,
+    pub created_at: DateTime<'a>,
+    pub pushed_at: Option<DateTime<'a>>,
+    pub html_url: Uri<'a>,
+    pub hooks_url: Option<UriTemplate<'a>>,
+    pub license_date: Option<Date<'a>>,
+    pub email: Option<Email<'a>>,
+    pub node_id: Option<&'a str>,
+    pub owner: Owner<'a>,
+    pub previous_owner: Option<OwnerPrevious>,
+    pub organization: Option<OwnerPrevious2<'a>>,
+}
+#[derive(Debug, Deserialize)]
+pub struct Owner<'a> {
+    pub login: &'a str,
+}
+#[derive(Debug, Deserialize)]
+pub struct OwnerPrevious {
+    pub id: usize,
+}
+#[derive(Debug, Deserialize)]
+pub struct OwnerPrevious2<'a> {
+    pub name: &'a str,
+}
@@ -0,0 +1,10 @@
+export interface Point {
+  /** latitude and longitude */
+  coordinates: [number, number];
+  labeled: [name: string, value: number, flag?: boolean];
+  path: [string, ...string[]];
+  mixed: [string, ...number[]];
+  rest: [first: number, ...rest: number[]];
+  pair: [{ key: string }, { value: number }];
+  empty: [];
+}
@@ -0,0 +1,20 @@
+#[derive(Debug, Deserialize)]
+pub struct PointPair<'a> {
+    pub key: &'a str,
+}
+#[derive(Debug, Deserialize)]
+pub struct PointPair1 {
+    pub value: usize,
+}
+#[derive(Debug, Deserialize)]
+pub struct Point<'a> {
+    #[doc = "latitude and longitude"]
+    pub coordinates: (usize, usize),
+    pub labeled: Vec<Value>,
+    #[serde(borrow = "'a")]
+    pub path: Vec<&'a str>,
+    pub mixed: Vec<Value>,
+    pub rest: Vec<usize>,
+    pub pair: (PointPair<'a>, PointPair1),
+    pub empty: (),
+}
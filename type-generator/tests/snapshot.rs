@@ -0,0 +1,86 @@
+//! regenerate Rust from `tests/fixtures/*.d.ts` and `tests/fixtures/*.schema.json` (JSON
+//! Schema) and compare with `tests/fixtures/*.rs`
+//!
+//! run with `UPDATE_SNAPSHOTS=1` to overwrite expected outputs.
+
+use std::{
+    env,
+    io::Write,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+use github_webhook_type_generator::{dts2ir, ir2rs, jsonschema2rs, Opt};
+
+fn rustfmt(rs: &str) -> String {
+    let mut child = Command::new("rustfmt")
+        .args(["--edition", "2021"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("failed to execute rustfmt");
+    let mut stdin = child.stdin.take().unwrap();
+    let input = rs.to_owned();
+    // write concurrently, or rustfmt may block on full stdout
+    let writer = std::thread::spawn(move || stdin.write_all(input.as_bytes()));
+    let output = child.wait_with_output().unwrap();
+    writer.join().unwrap().unwrap();
+    assert!(output.status.success(), "failed to format:\n{rs}");
+    String::from_utf8(output.stdout).unwrap()
+}
+
+fn fixtures() -> Vec<PathBuf> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+    let mut fixtures: Vec<_> = std::fs::read_dir(dir)
+        .unwrap()
+        .map(|e| e.unwrap().path())
+        .filter(|p| {
+            let p = p.to_str().unwrap();
+            p.ends_with(".d.ts") || p.ends_with(".schema.json")
+        })
+        .collect();
+    fixtures.sort();
+    fixtures
+}
+
+/// options of the fixture, e.g. `dedup*.d.ts` for opt-in merging
+fn opt(fixture: &Path) -> Opt {
+    let name = fixture.file_name().unwrap().to_str().unwrap();
+    Opt {
+        dedup: name.starts_with("dedup"),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn snapshot() {
+    let update = env::var_os("UPDATE_SNAPSHOTS").is_some();
+    let mut failed = Vec::new();
+    for fixture in fixtures() {
+        let expected_file = fixture.with_extension("").with_extension("rs");
+        let rs = if fixture.extension().unwrap() == "json" {
+            jsonschema2rs(&fixture)
+        } else {
+            ir2rs(dts2ir(&fixture), &mut opt(&fixture))
+        };
+        let actual = rustfmt(&rs.to_string());
+        if update {
+            std::fs::write(&expected_file, &actual).unwrap();
+            continue;
+        }
+        let expected = std::fs::read_to_string(&expected_file).unwrap_or_default();
+        if actual != expected {
+            eprintln!(
+                "--- {}\n{}\n+++ actual\n{}",
+                expected_file.display(),
+                expected,
+                actual
+            );
+            failed.push(fixture);
+        }
+    }
+    assert!(
+        failed.is_empty(),
+        "snapshot mismatch (run with UPDATE_SNAPSHOTS=1 to update): {failed:?}"
+    );
+}
@@ -0,0 +1,201 @@
+//! generate GitHub webhook payload types from `schema.d.ts` at compile time, without build script
+//!
+//! ```
+//! github_webhook_types_macro::github_webhook_types! {
+//!     pub mod payload {
+//!         // relative to the manifest directory of the crate
+//!         schema: "../type-generator/tests/fixtures/accessors.d.ts",
+//!         // derived in addition to `Debug` and `Deserialize`
+//!         derive: [Clone, PartialEq],
+//!         // `String` instead of `&'a str`, default: true
+//!         borrow: false,
+//!         // events to generate, default: all of them
+//!         events: ["star", "ping"],
+//!     }
+//! }
+//!
+//! let star: payload::Schema = serde_json::from_str(r#"{
+//!     "action": "created",
+//!     "repository": { "full_name": "sksat/github-webhook-rs" },
+//!     "sender": { "login": "sksat" }
+//! }"#).unwrap();
+//! assert_eq!(star.event_name(), "star");
+//! ```
+//!
+//! the generated code uses `serde` (with `derive`) and `serde_json`, which the crate invoking the
+//! macro has to depend on:
+//!
+//! ```toml
+//! [dependencies]
+//! serde = { version = "1", features = ["derive"] }
+//! serde_json = "1"
+//! ```
+//!
+//! unknown `events` are compile errors:
+//!
+//! ```compile_fail
+//! github_webhook_types_macro::github_webhook_types! {
+//!     mod payload {
+//!         schema: "../type-generator/tests/fixtures/accessors.d.ts",
+//!         events: ["stars"],
+//!     }
+//! }
+//! ```
+
+use std::path::PathBuf;
+
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, ToTokens};
+use syn::{
+    braced, bracketed,
+    parse::{Parse, ParseStream},
+    parse_macro_input,
+    punctuated::Punctuated,
+    Ident, LitBool, LitStr, Path, Token, Visibility,
+};
+
+use github_webhook_type_generator::{dts2ir, ir::RustSegment, ir2rs, split, Opt};
+
+struct Config {
+    vis: Visibility,
+    module: Ident,
+    schema: LitStr,
+    derive: Vec<Path>,
+    borrow: bool,
+    events: Option<Vec<LitStr>>,
+}
+
+impl Parse for Config {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let vis = input.parse()?;
+        input.parse::<Token![mod]>()?;
+        let module: Ident = input.parse()?;
+        let content;
+        braced!(content in input);
+
+        let mut schema = None;
+        let mut derive = Vec::new();
+        let mut borrow = true;
+        let mut events = None;
+        while !content.is_empty() {
+            let key: Ident = content.parse()?;
+            content.parse::<Token![:]>()?;
+            match key.to_string().as_str() {
+                "schema" => schema = Some(content.parse()?),
+                "derive" => {
+                    let list;
+                    bracketed!(list in content);
+                    derive = Punctuated::<Path, Token![,]>::parse_terminated(&list)?
+                        .into_iter()
+                        .collect();
+                }
+                "borrow" => borrow = content.parse::<LitBool>()?.value,
+                "events" => {
+                    let list;
+                    bracketed!(list in content);
+                    events = Some(
+                        Punctuated::<LitStr, Token![,]>::parse_terminated(&list)?
+                            .into_iter()
+                            .collect(),
+                    );
+                }
+                _ => {
+                    return Err(syn::Error::new(
+                        key.span(),
+                        "expected `schema`, `derive`, `borrow` or `events`",
+                    ))
+                }
+            }
+            if content.is_empty() {
+                break;
+            }
+            content.parse::<Token![,]>()?;
+        }
+        let schema = schema.ok_or_else(|| syn::Error::new(module.span(), "missing `schema`"))?;
+
+        Ok(Self {
+            vis,
+            module,
+            schema,
+            derive,
+            borrow,
+            events,
+        })
+    }
+}
+
+/// generate payload types into the module. see [the crate documentation](crate).
+#[proc_macro]
+pub fn github_webhook_types(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let config = parse_macro_input!(input as Config);
+    expand(config)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand(config: Config) -> syn::Result<TokenStream> {
+    let Config {
+        vis,
+        module,
+        schema,
+        derive,
+        borrow,
+        events,
+    } = config;
+
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR")
+        .map_err(|_| syn::Error::new(Span::call_site(), "CARGO_MANIFEST_DIR is not set"))?;
+    let path = PathBuf::from(manifest_dir).join(schema.value());
+    if !path.is_file() {
+        return Err(syn::Error::new(
+            schema.span(),
+            format!("schema not found: {}", path.display()),
+        ));
+    }
+
+    let mut opt = Opt {
+        owned: !borrow,
+        derive: derive
+            .iter()
+            .map(|p| p.to_token_stream().to_string())
+            .collect(),
+        ..Default::default()
+    };
+    let ir = dts2ir(&path);
+    if let Some(events) = events {
+        let known = split::event_names(&ir.segments, "Schema");
+        let unknown = events
+            .iter()
+            .filter(|e| !known.contains(&e.value()))
+            .map(|e| syn::Error::new(e.span(), format!("unknown event `{}`", e.value())))
+            .reduce(|mut errors, e| {
+                errors.combine(e);
+                errors
+            });
+        if let Some(errors) = unknown {
+            return Err(errors);
+        }
+        let events: Vec<String> = events.iter().map(LitStr::value).collect();
+        opt.add_pass(move |segments: &mut Vec<RustSegment>| {
+            let owners = split::event_owners(segments, "Schema");
+            split::exclude_events(segments, &owners, |e| events.iter().any(|x| x == e));
+        });
+    }
+    let types = ir2rs(ir, &mut opt);
+    // rebuild when the schema changes
+    let path = path.to_string_lossy();
+    Ok(quote! {
+        #vis mod #module {
+            #![allow(non_camel_case_types, non_snake_case, dead_code)]
+            #![allow(clippy::large_enum_variant)]
+
+            use serde::Deserialize;
+            use serde_json::Value;
+            use std::collections::HashMap;
+
+            const _: &[u8] = include_bytes!(#path);
+
+            #types
+        }
+    })
+}
@@ -0,0 +1,87 @@
+use github_webhook_types_macro::github_webhook_types;
+use owned::HasSender;
+
+github_webhook_types! {
+    mod owned {
+        schema: "../type-generator/tests/fixtures/accessors.d.ts",
+        derive: [Clone, PartialEq],
+        borrow: false,
+        events: ["star", "ping"],
+    }
+}
+
+github_webhook_types! {
+    mod borrowed {
+        schema: "../type-generator/tests/fixtures/accessors.d.ts",
+    }
+}
+
+// events sharing a literal `action` besides the generated `Schema::action()`
+github_webhook_types! {
+    mod shared_action {
+        schema: "../type-generator/tests/fixtures/shared_action.d.ts",
+    }
+}
+
+const STAR: &str = r#"{
+    "action": "created",
+    "repository": { "full_name": "sksat/github-webhook-rs" },
+    "sender": { "login": "sksat" }
+}"#;
+
+#[test]
+fn owned_with_derives() {
+    let star: owned::Schema = serde_json::from_str(STAR).unwrap();
+    let cloned = star.clone();
+    assert_eq!(star, cloned);
+    assert_eq!(star.event_name(), "star");
+    assert_eq!(star.action(), Some("created"));
+    // outlives the input
+    let login: String = {
+        let json = STAR.to_owned();
+        let star: owned::StarEvent = serde_json::from_str(&json).unwrap();
+        star.sender().unwrap().login.clone()
+    };
+    assert_eq!(login, "sksat");
+}
+
+#[test]
+fn borrowed() {
+    let star: borrowed::Schema = serde_json::from_str(STAR).unwrap();
+    assert_eq!(
+        star.repository().unwrap().full_name,
+        "sksat/github-webhook-rs"
+    );
+    let meta: borrowed::Schema =
+        serde_json::from_str(r#"{ "hook_id": 1, "sender": { "id": 1 } }"#).unwrap();
+    assert_eq!(meta.event_name(), "meta");
+}
+
+#[test]
+fn deserialize_event() {
+    let mut de = serde_json::Deserializer::from_str(STAR);
+    let star = borrowed::Schema::deserialize_event("star", &mut de).unwrap();
+    assert_eq!(star.unwrap().action(), Some("created"));
+    let mut de = serde_json::Deserializer::from_str(STAR);
+    let star =
+        borrowed::Schema::deserialize_event_action("star", Some("created"), &mut de).unwrap();
+    assert_eq!(star.unwrap().action(), Some("created"));
+    let mut de = serde_json::Deserializer::from_str(STAR);
+    assert!(borrowed::Schema::deserialize_event("ping", &mut de).is_err());
+    let mut de = serde_json::Deserializer::from_str(STAR);
+    assert!(borrowed::Schema::deserialize_event("unknown", &mut de)
+        .unwrap()
+        .is_none());
+}
+
+#[test]
+fn shared_action() {
+    let watch = r#"{ "action": "created", "sender": { "login": "sksat" } }"#;
+    let mut de = serde_json::Deserializer::from_str(watch);
+    let watch = shared_action::Schema::deserialize_event("watch", &mut de)
+        .unwrap()
+        .unwrap();
+    assert_eq!(watch.event_name(), "watch");
+    assert_eq!(watch.action(), None);
+    assert_eq!(watch.sender().unwrap().login, "sksat");
+}